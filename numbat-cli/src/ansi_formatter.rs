@@ -22,6 +22,7 @@ impl Formatter for ANSIFormatter {
             FormatType::TypeIdentifier => text.blue().italic(),
             FormatType::Operator => text.bold(),
             FormatType::Decorator => text.green(),
+            FormatType::Annotation => text.dimmed().italic(),
         })
         .to_string()
     }
@@ -39,6 +39,7 @@ impl Formatter for JqueryTerminalFormatter {
             FormatType::TypeIdentifier => Some("type-identifier"),
             FormatType::Operator => Some("operator"),
             FormatType::Decorator => Some("decorator"),
+            FormatType::Annotation => Some("annotation"),
         };
         jt_format(css_class, s)
     }
@@ -0,0 +1,40 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+use numbat::module_importer::BuiltinModuleImporter;
+use numbat::resolver::CodeSource;
+use numbat::Context;
+
+fn conversions(c: &mut Criterion) {
+    let importer = BuiltinModuleImporter::default();
+    let mut context = Context::new(importer);
+    let _ = context.interpret("use prelude", CodeSource::Text).unwrap();
+
+    c.bench_function("Convert between compound units", |b| {
+        b.iter(|| {
+            context
+                .interpret("60 km/h -> mph", CodeSource::Text)
+                .is_ok()
+        })
+    });
+
+    c.bench_function("Simplify compound units", |b| {
+        b.iter(|| {
+            context
+                .interpret("3 kWh / (2 h) * 5 min / (20 s)", CodeSource::Text)
+                .is_ok()
+        })
+    });
+
+    c.bench_function("Canonicalize large products", |b| {
+        b.iter(|| {
+            context
+                .interpret(
+                    "1 m·s·kg·A·K·mol·cd·N·J·W·Pa·Hz·V·Ω·C·F·T·Wb·H / (m·s·kg·A·K·mol·cd)",
+                    CodeSource::Text,
+                )
+                .is_ok()
+        })
+    });
+}
+
+criterion_group!(benches, conversions);
+criterion_main!(benches);
@@ -42,3 +42,71 @@ pub fn pretty_exponent(e: &Exponent) -> String {
         format!("^({})", e)
     }
 }
+
+pub fn superscript_integer(n: i32) -> String {
+    n.to_string()
+        .chars()
+        .map(|c| match c {
+            '-' => '⁻',
+            '0' => '⁰',
+            '1' => '¹',
+            '2' => '²',
+            '3' => '³',
+            '4' => '⁴',
+            '5' => '⁵',
+            '6' => '⁶',
+            '7' => '⁷',
+            '8' => '⁸',
+            '9' => '⁹',
+            _ => unreachable!(),
+        })
+        .collect()
+}
+
+pub fn ascii_exponent(e: &Exponent) -> String {
+    if e == &Ratio::from_integer(1) {
+        "".into()
+    } else if e.is_integer() {
+        format!("^{}", e)
+    } else {
+        format!("^({})", e)
+    }
+}
+
+/// Finds the rational number with a denominator of at most `max_denominator` that is
+/// exactly equal to `x` (as a floating point number), using continued fractions.
+/// Returns `None` if there is no such number, e.g. for irrational numbers like π.
+pub fn exact_rational_from_f64(x: f64, max_denominator: i128) -> Option<Rational> {
+    if !x.is_finite() {
+        return None;
+    }
+
+    // Numerators and denominators of the previous two convergents
+    let (mut h_prev, mut h) = (0i128, 1i128);
+    let (mut k_prev, mut k) = (1i128, 0i128);
+
+    let mut remainder = x;
+    loop {
+        let a = remainder.floor();
+        if a.abs() > i64::MAX as f64 {
+            return None;
+        }
+        let a = a as i128;
+
+        (h_prev, h) = (h, a.checked_mul(h)?.checked_add(h_prev)?);
+        (k_prev, k) = (k, a.checked_mul(k)?.checked_add(k_prev)?);
+        if k > max_denominator {
+            return None;
+        }
+
+        if h as f64 / k as f64 == x {
+            return Some(Rational::new(h, k));
+        }
+
+        let fractional_part = remainder - a as f64;
+        if fractional_part == 0.0 {
+            return None;
+        }
+        remainder = 1.0 / fractional_part;
+    }
+}
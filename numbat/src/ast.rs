@@ -508,9 +508,12 @@ impl ReplaceSpans for Expression {
         match self {
             Expression::Scalar(_, name) => Expression::Scalar(Span::dummy(), *name),
             Expression::Identifier(_, name) => Expression::Identifier(Span::dummy(), name.clone()),
-            Expression::UnitIdentifier(_, prefix, name, full_name) => {
-                Expression::UnitIdentifier(Span::dummy(), *prefix, name.clone(), full_name.clone())
-            }
+            Expression::UnitIdentifier(_, prefix, name, full_name) => Expression::UnitIdentifier(
+                Span::dummy(),
+                prefix.clone(),
+                name.clone(),
+                full_name.clone(),
+            ),
             Expression::UnaryOperator {
                 op,
                 expr,
@@ -91,7 +91,7 @@ impl BytecodeInterpreter {
                 self.vm.add_op1(Op::LoadConstant, *index);
 
                 if prefix != &Prefix::none() {
-                    let prefix_idx = self.vm.add_prefix(*prefix);
+                    let prefix_idx = self.vm.add_prefix(prefix.clone());
                     self.vm.add_op1(Op::ApplyPrefix, prefix_idx);
                 }
             }
@@ -397,16 +397,24 @@ impl BytecodeInterpreter {
                             ),
                             url: decorator::url(decorators),
                             description: decorator::description(decorators),
+                            deprecated: decorator::deprecation_note(decorators).cloned(),
                             binary_prefixes: decorators.contains(&Decorator::BinaryPrefixes),
                             metric_prefixes: decorators.contains(&Decorator::MetricPrefixes),
                         },
                     )
                     .map_err(RuntimeError::UnitRegistryError)?;
 
-                let constant_idx = self.vm.add_constant(Constant::Unit(Unit::new_base(
-                    unit_name,
-                    crate::decorator::get_canonical_unit_name(unit_name.as_str(), &decorators[..]),
-                )));
+                let constant_idx = self.vm.add_constant(Constant::Unit(
+                    Unit::new_base_with_dimension(
+                        unit_name,
+                        crate::decorator::get_canonical_unit_name(
+                            unit_name.as_str(),
+                            &decorators[..],
+                        ),
+                        &type_.to_concrete_type().to_string(),
+                    )
+                    .with_prefix_support(decorator::prefix_support(decorators)),
+                ));
                 for (name, _) in decorator::name_and_aliases(unit_name, decorators) {
                     self.unit_name_to_constant_index
                         .insert(name.into(), constant_idx);
@@ -444,6 +452,7 @@ impl BytecodeInterpreter {
                         canonical_name: decorator::get_canonical_unit_name(unit_name, decorators),
                         url: decorator::url(decorators),
                         description: decorator::description(decorators),
+                        deprecated: decorator::deprecation_note(decorators).cloned(),
                         binary_prefixes: decorators.contains(&Decorator::BinaryPrefixes),
                         metric_prefixes: decorators.contains(&Decorator::MetricPrefixes),
                     },
@@ -1,4 +1,7 @@
-use crate::{prefix_parser::AcceptsPrefix, unit::CanonicalName};
+use crate::{
+    prefix_parser::AcceptsPrefix,
+    unit::{CanonicalName, PrefixSupport},
+};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Decorator {
@@ -8,6 +11,8 @@ pub enum Decorator {
     Url(String),
     Name(String),
     Description(String),
+    /// Marks a name as obsolete, with an optional suggestion for a replacement
+    Deprecated(Option<String>),
 }
 
 pub fn name_and_aliases<'a>(
@@ -37,23 +42,28 @@ pub fn name_and_aliases<'a>(
     }
 }
 
+/// Returns the name that is used when printing a unit. This is the alias that accepts
+/// short prefixes (like `m` for `meter`). If there are several such aliases, the shortest
+/// one is chosen, with ties broken lexicographically, so the result does not depend on the
+/// order of the aliases. Without any short alias, the unit name itself is used.
 pub fn get_canonical_unit_name(unit_name: &str, decorators: &[Decorator]) -> CanonicalName {
-    for decorator in decorators {
-        if let Decorator::Aliases(aliases) = decorator {
-            for (alias, accepts_prefix) in aliases {
-                match accepts_prefix {
-                    &Some(ap) if ap.short => {
-                        return CanonicalName::new(alias, ap);
-                    }
-                    _ => {}
-                }
-            }
-        }
-    }
-    CanonicalName {
-        name: unit_name.into(),
-        accepts_prefix: AcceptsPrefix::only_long(),
-    }
+    decorators
+        .iter()
+        .filter_map(|decorator| match decorator {
+            Decorator::Aliases(aliases) => Some(aliases),
+            _ => None,
+        })
+        .flatten()
+        .filter_map(|(alias, accepts_prefix)| match accepts_prefix {
+            Some(ap) if ap.short => Some((alias, *ap)),
+            _ => None,
+        })
+        .min_by(|(a, _), (b, _)| a.chars().count().cmp(&b.chars().count()).then(a.cmp(b)))
+        .map(|(alias, ap)| CanonicalName::new(alias, ap))
+        .unwrap_or_else(|| CanonicalName {
+            name: unit_name.into(),
+            accepts_prefix: AcceptsPrefix::only_long(),
+        })
 }
 
 pub fn name(decorators: &[Decorator]) -> Option<String> {
@@ -74,6 +84,10 @@ pub fn url(decorators: &[Decorator]) -> Option<String> {
     None
 }
 
+/// Returns the description of an item. Multiple `@description` decorators are joined
+/// into a multi-line description (one line per decorator, each ending in `\n`). The
+/// help output relies on this to show the first line as a summary, which is why
+/// this returns an owned string instead of borrowing from the decorators.
 pub fn description(decorators: &[Decorator]) -> Option<String> {
     let mut description = String::new();
     for decorator in decorators {
@@ -89,6 +103,22 @@ pub fn description(decorators: &[Decorator]) -> Option<String> {
     }
 }
 
+/// Returns `Some(suggestion)` if the decorators mark the item as deprecated.
+pub fn deprecation_note(decorators: &[Decorator]) -> Option<&Option<String>> {
+    decorators.iter().find_map(|decorator| match decorator {
+        Decorator::Deprecated(suggestion) => Some(suggestion),
+        _ => None,
+    })
+}
+
+/// The kinds of prefixes that a unit accepts (`@metric_prefixes`, `@binary_prefixes`).
+pub fn prefix_support(decorators: &[Decorator]) -> PrefixSupport {
+    PrefixSupport {
+        metric: decorators.contains(&Decorator::MetricPrefixes),
+        binary: decorators.contains(&Decorator::BinaryPrefixes),
+    }
+}
+
 pub fn contains_aliases_with_prefixes(decorates: &[Decorator]) -> bool {
     for decorator in decorates {
         if let Decorator::Aliases(aliases) = decorator {
@@ -110,3 +140,165 @@ pub fn contains_aliases(decorators: &[Decorator]) -> bool {
 
     false
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn canonical_unit_name_is_deterministic() {
+        let metre = ("metre".to_string(), Some(AcceptsPrefix::only_short()));
+        let m = ("m".to_string(), Some(AcceptsPrefix::only_short()));
+        let expected = CanonicalName::new("m", AcceptsPrefix::only_short());
+
+        assert_eq!(
+            get_canonical_unit_name(
+                "meter",
+                &[Decorator::Aliases(vec![metre.clone(), m.clone()])]
+            ),
+            expected
+        );
+        assert_eq!(
+            get_canonical_unit_name("meter", &[Decorator::Aliases(vec![m, metre])]),
+            expected
+        );
+
+        let aliases = |names: &[&str]| {
+            Decorator::Aliases(
+                names
+                    .iter()
+                    .map(|n| (n.to_string(), Some(AcceptsPrefix::only_short())))
+                    .collect(),
+            )
+        };
+        assert_eq!(
+            get_canonical_unit_name("x", &[aliases(&["b", "a"])]).name,
+            "a"
+        );
+        assert_eq!(
+            get_canonical_unit_name("x", &[aliases(&["a", "b"])]).name,
+            "a"
+        );
+        assert_eq!(
+            get_canonical_unit_name("liter", &[aliases(&["liters", "l", "L"])]).name,
+            "L"
+        );
+        assert_eq!(
+            get_canonical_unit_name("liter", &[aliases(&["L", "l", "liters"])]).name,
+            "L"
+        );
+
+        assert_eq!(
+            get_canonical_unit_name(
+                "meter",
+                &[Decorator::Aliases(vec![("meters".into(), None)])]
+            ),
+            CanonicalName::new("meter", AcceptsPrefix::only_long())
+        );
+    }
+
+    #[test]
+    fn prefix_support() {
+        assert_eq!(
+            super::prefix_support(&[Decorator::MetricPrefixes, Decorator::BinaryPrefixes]),
+            PrefixSupport {
+                metric: true,
+                binary: true,
+            }
+        );
+        assert_eq!(
+            super::prefix_support(&[Decorator::Aliases(vec![(
+                "h".into(),
+                Some(AcceptsPrefix::only_short())
+            )])]),
+            PrefixSupport::default()
+        );
+    }
+
+    #[test]
+    fn url() {
+        let decorators = [
+            Decorator::MetricPrefixes,
+            Decorator::Aliases(vec![
+                ("meters".into(), None),
+                ("m".into(), Some(AcceptsPrefix::only_short())),
+            ]),
+            Decorator::Url("https://en.wikipedia.org/wiki/Metre".into()),
+        ];
+        assert_eq!(
+            super::url(&decorators).as_deref(),
+            Some("https://en.wikipedia.org/wiki/Metre")
+        );
+        assert_eq!(super::url(&[Decorator::Name("Meter".into())]), None);
+
+        let name = "meter".to_string();
+        assert_eq!(
+            name_and_aliases(&name, &decorators)
+                .map(|(n, _)| n.as_str())
+                .collect::<Vec<_>>(),
+            ["meter", "meters", "m"]
+        );
+    }
+
+    #[test]
+    fn description_is_metadata_only() {
+        let decorators = [
+            Decorator::Description("SI unit of force".into()),
+            Decorator::Aliases(vec![("N".into(), Some(AcceptsPrefix::only_short()))]),
+        ];
+        assert_eq!(
+            description(&decorators).as_deref(),
+            Some("SI unit of force\n")
+        );
+        assert_eq!(description(&[Decorator::MetricPrefixes]), None);
+
+        let name = "newton".to_string();
+        assert_eq!(
+            name_and_aliases(&name, &decorators)
+                .map(|(n, _)| n.as_str())
+                .collect::<Vec<_>>(),
+            ["newton", "N"]
+        );
+        assert_eq!(
+            get_canonical_unit_name(&name, &decorators),
+            CanonicalName::new("N", AcceptsPrefix::only_short())
+        );
+    }
+
+    #[test]
+    fn multi_line_description() {
+        let decorators = [
+            Decorator::Description("SI unit of force.".into()),
+            Decorator::Url("https://en.wikipedia.org/wiki/Newton_(unit)".into()),
+            Decorator::Description("Equal to 1 kg·m/s².".into()),
+        ];
+        let description = description(&decorators).unwrap();
+        assert_eq!(description, "SI unit of force.\nEqual to 1 kg·m/s².\n");
+        assert_eq!(
+            description.lines().collect::<Vec<_>>(),
+            ["SI unit of force.", "Equal to 1 kg·m/s²."]
+        );
+    }
+
+    #[test]
+    fn deprecation() {
+        let decorators = [
+            Decorator::Aliases(vec![("newtons".into(), None)]),
+            Decorator::Deprecated(Some("newton".into())),
+        ];
+        assert_eq!(
+            deprecation_note(&decorators),
+            Some(&Some("newton".to_string()))
+        );
+        assert_eq!(
+            deprecation_note(&[Decorator::Deprecated(None)]),
+            Some(&None)
+        );
+        assert_eq!(deprecation_note(&[]), None);
+        assert_eq!(deprecation_note(&[Decorator::MetricPrefixes]), None);
+
+        // deprecated names can still be used
+        let name = "newtons".to_string();
+        assert!(name_and_aliases(&name, &decorators).any(|(n, _)| n == "newtons"));
+    }
+}
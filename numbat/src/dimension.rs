@@ -1,4 +1,9 @@
-use crate::arithmetic::{Exponent, Power};
+use std::fmt::Display;
+
+use itertools::Itertools;
+use num_traits::Zero;
+
+use crate::arithmetic::{pretty_exponent, Exponent, Power};
 use crate::ast::{TypeExpression, TypeParameterBound};
 use crate::registry::{BaseRepresentation, Registry, Result};
 use crate::span::Span;
@@ -94,6 +99,59 @@ impl DimensionRegistry {
     }
 }
 
+/// A physical base dimension like `Length` or `Time`.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct BaseDimension(pub String);
+
+/// The physical dimension of a unit as a product of powers of base dimensions, e.g.
+/// `Length·Time⁻¹` for `km/h`. In contrast to the base unit representation, this
+/// does not depend on the choice of base units: two base units of the same physical
+/// dimension have the same `Dimension`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct Dimension(Vec<(BaseDimension, Exponent)>);
+
+impl Dimension {
+    pub fn scalar() -> Self {
+        Self::default()
+    }
+
+    /// Combines the given factors. Powers of the same base dimension are merged and
+    /// factors with a zero exponent are removed.
+    pub fn from_factors(factors: impl IntoIterator<Item = (BaseDimension, Exponent)>) -> Self {
+        let mut merged: Vec<(BaseDimension, Exponent)> = vec![];
+        for (base_dimension, exponent) in factors {
+            match merged.iter_mut().find(|(d, _)| d == &base_dimension) {
+                Some((_, e)) => *e += exponent,
+                None => merged.push((base_dimension, exponent)),
+            }
+        }
+        merged.retain(|(_, exponent)| !exponent.is_zero());
+        merged.sort();
+        Dimension(merged)
+    }
+
+    pub fn is_scalar(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &(BaseDimension, Exponent)> {
+        self.0.iter()
+    }
+}
+
+impl Display for Dimension {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.is_scalar() {
+            return write!(f, "Scalar");
+        }
+        let factors = self
+            .iter()
+            .map(|(BaseDimension(name), exponent)| format!("{name}{}", pretty_exponent(exponent)))
+            .join("·");
+        write!(f, "{factors}")
+    }
+}
+
 #[test]
 fn basic() {
     use crate::arithmetic::Rational;
@@ -212,3 +270,40 @@ fn fails_if_same_dimension_is_added_twice() {
     assert!(registry.add_base_dimension("Length").is_ok());
     assert!(registry.add_base_dimension("Length").is_err());
 }
+
+#[test]
+fn dimension_of_units() {
+    use crate::prefix_parser::AcceptsPrefix;
+    use crate::unit::{CanonicalName, Unit};
+
+    let length = |name: &str| {
+        Unit::new_base_with_dimension(
+            name,
+            CanonicalName::new(name, AcceptsPrefix::none()),
+            "Length",
+        )
+    };
+    let meter = length("meter");
+    let foot = length("foot");
+    assert_ne!(meter, foot);
+    assert_eq!(meter.dimension(), foot.dimension());
+    assert_eq!(
+        meter.dimension(),
+        Dimension::from_factors([(BaseDimension("Length".into()), Exponent::from_integer(1))])
+    );
+
+    let mass = Unit::new_base_with_dimension(
+        "gram",
+        CanonicalName::new("g", AcceptsPrefix::none()),
+        "Mass",
+    );
+    let time = Unit::new_base_with_dimension(
+        "second",
+        CanonicalName::new("s", AcceptsPrefix::none()),
+        "Time",
+    );
+    let newton = mass * meter.clone() / time.powi(2);
+    assert_eq!(newton.dimension().to_string(), "Length·Mass·Time⁻²");
+    assert_eq!((meter.clone() / foot).dimension(), Dimension::scalar());
+    assert_eq!(Unit::scalar().dimension().to_string(), "Scalar");
+}
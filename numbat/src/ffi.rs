@@ -551,9 +551,9 @@ fn assert_eq(_: &mut ExecutionContext, args: &[Value]) -> ControlFlow {
                         ControlFlow::Continue(())
                     } else {
                         ControlFlow::Break(RuntimeError::AssertEq3Failed(
-                            lhs.clone(),
-                            rhs.clone(),
-                            eps.clone(),
+                            Box::new(lhs.clone()),
+                            Box::new(rhs.clone()),
+                            Box::new(eps.clone()),
                         ))
                     }
                 }
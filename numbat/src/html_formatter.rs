@@ -1,5 +1,5 @@
 use crate::buffered_writer::BufferedWriter;
-use crate::markup::{FormatType, FormattedString, Formatter};
+use crate::markup::{superscript_to_ascii, FormatType, FormattedString, Formatter};
 
 use termcolor::{Color, WriteColor};
 
@@ -19,6 +19,46 @@ pub fn html_format(class: Option<&str>, content: &str) -> String {
     }
 }
 
+/// Like [`html_format`] for units, but renders exponents (`m²`, `m^(1/2)`)
+/// as `<sup>` elements.
+fn html_format_unit(content: &str) -> String {
+    if content.is_empty() {
+        return "".into();
+    }
+
+    let mut result = String::new();
+    let mut chars = content.chars().peekable();
+    while let Some(c) = chars.next() {
+        let mut exponent = String::new();
+        if c == '^' {
+            if chars.peek() == Some(&'(') {
+                chars.next();
+                exponent.extend(chars.by_ref().take_while(|&c| c != ')'));
+            } else {
+                while let Some(&c) = chars.peek().filter(|c| c.is_ascii_digit() || **c == '-') {
+                    exponent.push(c);
+                    chars.next();
+                }
+            }
+        } else if let Some(d) = superscript_to_ascii(c) {
+            exponent.push(d);
+            while let Some(d) = chars.peek().and_then(|&c| superscript_to_ascii(c)) {
+                exponent.push(d);
+                chars.next();
+            }
+        } else {
+            result.push_str(&html_escape::encode_text(&c.to_string()));
+            continue;
+        }
+        result.push_str(&format!(
+            "<sup>{}</sup>",
+            html_escape::encode_text(&exponent)
+        ));
+    }
+
+    format!("<span class=\"numbat-unit\">{result}</span>")
+}
+
 impl Formatter for HtmlFormatter {
     fn format_part(
         &self,
@@ -32,11 +72,12 @@ impl Formatter for HtmlFormatter {
             FormatType::String => Some("string"),
             FormatType::Keyword => Some("keyword"),
             FormatType::Value => Some("value"),
-            FormatType::Unit => Some("unit"),
+            FormatType::Unit => return html_format_unit(s),
             FormatType::Identifier => Some("identifier"),
             FormatType::TypeIdentifier => Some("type-identifier"),
             FormatType::Operator => Some("operator"),
             FormatType::Decorator => Some("decorator"),
+            FormatType::Annotation => Some("annotation"),
         };
         html_format(css_class, s)
     }
@@ -111,3 +152,46 @@ impl WriteColor for HtmlWriter {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unit_exponents() {
+        assert_eq!(
+            html_format_unit("m/s²"),
+            "<span class=\"numbat-unit\">m/s<sup>2</sup></span>"
+        );
+        assert_eq!(
+            html_format_unit("s⁻¹"),
+            "<span class=\"numbat-unit\">s<sup>-1</sup></span>"
+        );
+        assert_eq!(
+            html_format_unit("m^(1/2)"),
+            "<span class=\"numbat-unit\">m<sup>1/2</sup></span>"
+        );
+        assert_eq!(
+            html_format_unit("°"),
+            "<span class=\"numbat-unit\">°</span>"
+        );
+        assert_eq!(html_format_unit(""), "");
+    }
+
+    #[test]
+    fn annotation() {
+        use crate::markup as m;
+
+        let markup =
+            m::value("3") + m::space() + m::unit("m") + m::space() + m::annotation("[Length]");
+        assert_eq!(
+            HtmlFormatter {}.format(&markup, false),
+            "<span class=\"numbat-value\">3</span> <span class=\"numbat-unit\">m</span> \
+             <span class=\"numbat-annotation\">[Length]</span>"
+        );
+        assert_eq!(
+            m::PlainTextFormatter {}.format(&markup, false),
+            "3 m [Length]"
+        );
+    }
+}
@@ -30,7 +30,7 @@ pub enum RuntimeError {
     #[error("Assertion failed because the following two values are not the same:\n  {0}\n  {1}")]
     AssertEq2Failed(Value, Value),
     #[error("Assertion failed because the following two quantities differ by more than {2}:\n  {0}\n  {1}")]
-    AssertEq3Failed(Quantity, Quantity, Quantity),
+    AssertEq3Failed(Box<Quantity>, Box<Quantity>, Box<Quantity>),
     #[error("Could not load exchange rates from European Central Bank.")]
     CouldNotLoadExchangeRates,
     #[error("User error: {0}")]
@@ -235,6 +235,26 @@ mod tests {
         }
     }
 
+    #[test]
+    fn auto_prefix_respects_prefix_decorators() {
+        let with_auto_prefix = |input: &str| match get_interpreter_result(input).unwrap() {
+            InterpreterResult::Value(value) => {
+                value.unsafe_as_quantity().with_auto_prefix().to_string()
+            }
+            _ => panic!(),
+        };
+
+        assert_eq!(with_auto_prefix("3400 m"), "3.4 km");
+        assert_eq!(with_auto_prefix("7200 s"), "7200 s");
+        assert_eq!(with_auto_prefix("2500 Hz"), "2500 Hz");
+        assert_eq!(
+            with_auto_prefix(
+                "@metric_prefixes\n@aliases(ft: short)\nunit foot = 0.3048 m\n2500 ft"
+            ),
+            "2.5 kft"
+        );
+    }
+
     #[test]
     fn simple_arithmetic() {
         assert_evaluates_to_scalar("0", 0.0);
@@ -276,6 +296,23 @@ mod tests {
         );
     }
 
+    #[test]
+    fn conversion_between_base_units_of_the_same_dimension() {
+        assert_runtime_error(
+            "1 meter -> alternative_length_base_unit",
+            RuntimeError::QuantityError(QuantityError::AmbiguousBaseUnits(
+                Unit::new_base(
+                    "meter",
+                    CanonicalName::new("m", AcceptsPrefix::only_short()),
+                ),
+                Unit::new_base(
+                    "alternative_length_base_unit",
+                    CanonicalName::new("alternative_length_base_unit", AcceptsPrefix::only_long()),
+                ),
+            )),
+        );
+    }
+
     #[test]
     fn arithmetic_with_units() {
         use crate::unit::Unit;
@@ -39,6 +39,7 @@ mod typechecker;
 mod typed_ast;
 pub mod unicode_input;
 mod unit;
+mod unit_parser;
 mod unit_registry;
 pub mod value;
 mod vm;
@@ -327,6 +328,14 @@ impl Context {
                     }
                 }
 
+                if let Some(suggestion) = &md.deprecated {
+                    help += m::text("Deprecated");
+                    if let Some(suggestion) = suggestion {
+                        help += m::text(", use ") + m::unit(suggestion) + m::text(" instead");
+                    }
+                    help += m::nl();
+                }
+
                 if matches!(md.type_, Type::Dimension(d) if d.is_scalar()) {
                     help += m::text("A dimensionless unit ([")
                         + md.readable_type
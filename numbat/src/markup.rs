@@ -14,6 +14,8 @@ pub enum FormatType {
     TypeIdentifier,
     Operator,
     Decorator,
+    /// Additional information like a dimension (`[Length]`), usually styled subtly.
+    Annotation,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -32,6 +34,31 @@ impl Markup {
     pub fn from(f: FormattedString) -> Self {
         Self(vec![f])
     }
+
+    /// Concatenates the given parts with a separator in between, like `a, b, c`.
+    pub fn join(parts: impl IntoIterator<Item = Markup>, separator: Markup) -> Markup {
+        let mut result = empty();
+        for (i, part) in parts.into_iter().enumerate() {
+            if i > 0 {
+                result += separator.clone();
+            }
+            result += part;
+        }
+        result
+    }
+
+    /// Appends `part` to this markup, same as `+=`.
+    pub fn push(&mut self, part: Markup) {
+        *self += part;
+    }
+}
+
+impl Extend<Markup> for Markup {
+    fn extend<I: IntoIterator<Item = Markup>>(&mut self, iter: I) {
+        for part in iter {
+            self.push(part);
+        }
+    }
 }
 
 impl Display for Markup {
@@ -170,6 +197,14 @@ pub fn decorator(text: impl AsRef<str>) -> Markup {
     ))
 }
 
+pub fn annotation(text: impl AsRef<str>) -> Markup {
+    Markup::from(FormattedString(
+        OutputType::Normal,
+        FormatType::Annotation,
+        text.as_ref().to_string(),
+    ))
+}
+
 pub fn nl() -> Markup {
     Markup::from(FormattedString(
         OutputType::Normal,
@@ -209,3 +244,321 @@ impl Formatter for PlainTextFormatter {
         text.clone()
     }
 }
+
+/// Formats markup as plain text, like [`PlainTextFormatter`], but with configurable
+/// separators for numbers in values, for example `1.234,5` with a decimal comma and
+/// a dot as thousands separator.
+pub struct LocalizedPlainTextFormatter {
+    pub decimal_separator: char,
+    pub thousands_separator: Option<char>,
+}
+
+impl Default for LocalizedPlainTextFormatter {
+    fn default() -> Self {
+        Self {
+            decimal_separator: '.',
+            thousands_separator: None,
+        }
+    }
+}
+
+impl LocalizedPlainTextFormatter {
+    fn localize_value(&self, text: &str) -> String {
+        if self.decimal_separator == '.' && self.thousands_separator.is_none() {
+            return text.to_string();
+        }
+
+        let mut output = String::new();
+        let mut chars = text.chars().peekable();
+        while let Some(c) = chars.next() {
+            if !c.is_ascii_digit() {
+                output.push(c);
+                continue;
+            }
+
+            let mut integer_part = String::from(c);
+            while let Some(&c) = chars.peek() {
+                if !(c.is_ascii_digit() || c == '_') {
+                    break;
+                }
+                integer_part.push(c);
+                chars.next();
+            }
+
+            match self.thousands_separator {
+                Some(separator) => {
+                    let digits: Vec<char> = integer_part.chars().filter(|&c| c != '_').collect();
+                    for (i, digit) in digits.iter().enumerate() {
+                        if i > 0 && (digits.len() - i) % 3 == 0 {
+                            output.push(separator);
+                        }
+                        output.push(*digit);
+                    }
+                }
+                None => output.push_str(&integer_part),
+            }
+
+            let mut lookahead = chars.clone();
+            if lookahead.next() == Some('.') && lookahead.peek().is_some_and(char::is_ascii_digit) {
+                chars.next();
+                output.push(self.decimal_separator);
+                while let Some(&c) = chars.peek() {
+                    if !c.is_ascii_digit() {
+                        break;
+                    }
+                    output.push(c);
+                    chars.next();
+                }
+            }
+        }
+        output
+    }
+}
+
+impl Formatter for LocalizedPlainTextFormatter {
+    fn format_part(&self, FormattedString(_, format_type, text): &FormattedString) -> String {
+        match format_type {
+            FormatType::Value => self.localize_value(text),
+            _ => text.clone(),
+        }
+    }
+}
+
+/// Converts a superscript character (like `²` or `⁻`) to its ASCII counterpart.
+pub(crate) fn superscript_to_ascii(c: char) -> Option<char> {
+    match c {
+        '⁻' => Some('-'),
+        '⁰' => Some('0'),
+        '¹' => Some('1'),
+        '²' => Some('2'),
+        '³' => Some('3'),
+        '⁴' => Some('4'),
+        '⁵' => Some('5'),
+        '⁶' => Some('6'),
+        '⁷' => Some('7'),
+        '⁸' => Some('8'),
+        '⁹' => Some('9'),
+        _ => None,
+    }
+}
+
+fn escape_latex(s: &str) -> String {
+    let mut result = String::new();
+    for c in s.chars() {
+        match c {
+            '\\' => result.push_str("\\textbackslash{}"),
+            '%' | '$' | '#' | '&' | '_' | '{' | '}' => {
+                result.push('\\');
+                result.push(c);
+            }
+            'µ' => result.push_str("\\mu "),
+            c => result.push(c),
+        }
+    }
+    result
+}
+
+/// Renders a number like `1.5e+10`, `7.3×10³` or `123_456` in LaTeX.
+fn latex_number(s: &str) -> String {
+    let s = s.replace('_', "\\,");
+    if let Some((mantissa, exponent)) = s.split_once('e') {
+        format!(
+            "{mantissa}\\times 10^{{{}}}",
+            exponent.trim_start_matches('+')
+        )
+    } else if let Some((mantissa, exponent)) = s.split_once("×10") {
+        let exponent: String = exponent
+            .chars()
+            .map(|c| superscript_to_ascii(c).unwrap_or(c))
+            .collect();
+        format!("{mantissa}\\times 10^{{{exponent}}}")
+    } else {
+        s
+    }
+}
+
+/// Renders a unit like `kg/(m·s²)` in LaTeX.
+fn latex_unit(s: &str) -> String {
+    let mut result = String::new();
+    let mut name = String::new();
+
+    let flush_name = |name: &mut String, result: &mut String| {
+        if !name.is_empty() {
+            result.push_str(&format!("\\mathrm{{{}}}", escape_latex(name).trim_end()));
+            name.clear();
+        }
+    };
+
+    let mut chars = s.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '·' | '/' | '(' | ')' | '°' | '^' => {
+                flush_name(&mut name, &mut result);
+                match c {
+                    '·' => result.push_str("\\cdot"),
+                    '°' => result.push_str("^\\circ"),
+                    '^' => {
+                        let mut exponent = String::new();
+                        if chars.peek() == Some(&'(') {
+                            chars.next();
+                            for c in chars.by_ref() {
+                                if c == ')' {
+                                    break;
+                                }
+                                exponent.push(c);
+                            }
+                        } else {
+                            while let Some(&c) = chars.peek() {
+                                if !c.is_ascii_digit() {
+                                    break;
+                                }
+                                exponent.push(c);
+                                chars.next();
+                            }
+                        }
+                        result.push_str(&format!("^{{{exponent}}}"));
+                    }
+                    c => result.push(c),
+                }
+            }
+            c if superscript_to_ascii(c).is_some() => {
+                flush_name(&mut name, &mut result);
+                let mut exponent: String = superscript_to_ascii(c).into_iter().collect();
+                while let Some(d) = chars.peek().and_then(|&c| superscript_to_ascii(c)) {
+                    exponent.push(d);
+                    chars.next();
+                }
+                result.push_str(&format!("^{{{exponent}}}"));
+            }
+            c => name.push(c),
+        }
+    }
+    flush_name(&mut name, &mut result);
+
+    result
+}
+
+/// Renders markup as (math mode) LaTeX, e.g. `9.81\,\mathrm{m}/\mathrm{s}^{2}`.
+pub struct LatexFormatter;
+
+impl Formatter for LatexFormatter {
+    fn format_part(&self, FormattedString(_, format_type, text): &FormattedString) -> String {
+        match format_type {
+            FormatType::Whitespace if text == " " => "\\,".into(),
+            FormatType::Whitespace => text.clone(),
+            FormatType::Value => latex_number(text),
+            FormatType::Unit => latex_unit(text),
+            _ => escape_latex(text),
+        }
+    }
+}
+
+/// Renders markup as LaTeX inline math for Markdown, e.g. `$9.81\,\mathrm{m}/\mathrm{s}^{2}$`.
+pub struct MarkdownFormatter;
+
+impl Formatter for MarkdownFormatter {
+    fn format_part(&self, part: &FormattedString) -> String {
+        LatexFormatter {}.format_part(part)
+    }
+
+    fn format(&self, markup: &Markup, indent: bool) -> String {
+        format!("${}$", LatexFormatter {}.format(markup, indent))
+    }
+}
+
+/// Replaces non-ASCII symbols: `·` becomes `*`, `µ` becomes `u`, `°` becomes `deg`
+/// and superscript exponents become `^n`.
+fn ascii_text(s: &str) -> String {
+    let mut result = String::new();
+    let mut chars = s.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '·' | '×' => result.push('*'),
+            'µ' => result.push('u'),
+            '°' => result.push_str("deg"),
+            c if superscript_to_ascii(c).is_some() => {
+                result.push('^');
+                result.extend(superscript_to_ascii(c));
+                while let Some(d) = chars.peek().and_then(|&c| superscript_to_ascii(c)) {
+                    result.push(d);
+                    chars.next();
+                }
+            }
+            c => result.push(c),
+        }
+    }
+    result
+}
+
+/// Renders markup using ASCII characters only, e.g. `9.81 m/s^2`.
+pub struct AsciiFormatter;
+
+impl Formatter for AsciiFormatter {
+    fn format_part(&self, FormattedString(_, format_type, text): &FormattedString) -> String {
+        match format_type {
+            FormatType::Value => match text.split_once("×10") {
+                Some((mantissa, exponent)) => {
+                    let exponent: String = exponent
+                        .chars()
+                        .map(|c| superscript_to_ascii(c).unwrap_or(c))
+                        .collect();
+                    format!("{mantissa}e{exponent}")
+                }
+                None => ascii_text(text),
+            },
+            // `90°` is printed without a space, but `90deg` would be hard to read
+            FormatType::Unit if text.starts_with('°') => format!(" {}", ascii_text(text)),
+            _ => ascii_text(text),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn plain_text_separators() {
+        let markup = value("1234.5") + space() + unit("m");
+        assert_eq!(PlainTextFormatter {}.format(&markup, false), "1234.5 m");
+        assert_eq!(
+            LocalizedPlainTextFormatter::default().format(&markup, false),
+            "1234.5 m"
+        );
+
+        let german = LocalizedPlainTextFormatter {
+            decimal_separator: ',',
+            thousands_separator: Some('.'),
+        };
+        assert_eq!(german.format(&markup, false), "1.234,5 m");
+        assert_eq!(german.format(&value("1_234_567"), false), "1.234.567");
+        assert_eq!(german.format(&value("-0.25"), false), "-0,25");
+        assert_eq!(german.format(&value("7.3×10³"), false), "7,3×10³");
+        assert_eq!(german.format(&(value("90") + unit("°")), false), "90°");
+
+        // Only values are affected
+        assert_eq!(german.format(&text("1234.5"), false), "1234.5");
+
+        let decimal_comma = LocalizedPlainTextFormatter {
+            decimal_separator: ',',
+            thousands_separator: None,
+        };
+        assert_eq!(decimal_comma.format(&value("1234.5"), false), "1234,5");
+    }
+
+    #[test]
+    fn join_and_push() {
+        let units = Markup::join([unit("m"), unit("s"), unit("kg")], operator(",") + space());
+        assert_eq!(PlainTextFormatter {}.format(&units, false), "m, s, kg");
+        assert_eq!(
+            units,
+            unit("m") + operator(",") + space() + unit("s") + operator(",") + space() + unit("kg")
+        );
+        assert_eq!(Markup::join([], space()), empty());
+
+        let mut markup = value("1");
+        markup.push(space());
+        markup.extend([unit("m"), operator("/"), unit("s")]);
+        assert_eq!(PlainTextFormatter {}.format(&markup, false), "1 m/s");
+    }
+}
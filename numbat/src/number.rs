@@ -1,41 +1,490 @@
-use num_traits::{Pow, ToPrimitive};
+use num_complex::Complex64;
+use num_rational::Ratio;
+use num_traits::{CheckedAdd, CheckedDiv, CheckedMul, CheckedSub, One, Pow, ToPrimitive, Zero};
+use thiserror::Error;
 
-#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)] // TODO: we probably want to remove 'Copy' once we move to a more sophisticated numerical type
-pub struct Number(pub f64);
+use crate::arithmetic::Rational;
+
+#[derive(Debug, Clone, Error, PartialEq, Eq)]
+pub enum ParseNumberError {
+    #[error("Empty number literal")]
+    Empty,
+
+    #[error("Unexpected character in number literal: '{0}'")]
+    UnexpectedCharacter(char),
+
+    #[error("Invalid number literal: '{0}'")]
+    Invalid(String),
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy)] // TODO: we probably want to remove 'Copy' once we move to a more sophisticated numerical type
+pub enum Number {
+    Real(f64),
+    /// A complex number. The imaginary part is always non-zero, complex results
+    /// with a vanishing imaginary part are stored as `Real`.
+    Complex(Complex64),
+    /// A measured value with a (one standard deviation) uncertainty. Errors are
+    /// propagated to first order, assuming uncorrelated inputs. Combining a measured
+    /// value with a complex number is not supported and results in NaN.
+    Measured {
+        value: f64,
+        uncertainty: f64,
+    },
+    /// A closed interval `[lo, hi]`. Arithmetic is rounded outwards, such that the
+    /// result always contains the exact result. Combining an interval with a complex
+    /// or measured value is not supported and results in NaN.
+    Interval {
+        lo: f64,
+        hi: f64,
+    },
+    /// An exact rational number. Arithmetic between rational numbers stays exact and
+    /// falls back to `Real` on overflow, or if any operand is not rational. This is
+    /// not an arbitrary precision type: the components are 64 bit wide in order to keep
+    /// `Number` small and `Copy`, so results with larger numerators or denominators
+    /// silently become floating point numbers. In particular, conversion factors are
+    /// only exact up to `10^18` (the exa prefix). Zetta, yotta, ronna and quetta, as
+    /// well as their inverses, are floating point numbers.
+    ///
+    /// Comparisons are done on the nearest `f64` value, also between two rational
+    /// numbers. This keeps equality transitive and consistent with `Hash` and
+    /// `PartialOrd` when rational and real numbers are mixed.
+    Rational(Ratio<i64>),
+}
+
+impl PartialEq for Number {
+    fn eq(&self, other: &Self) -> bool {
+        match (self.demote_rational(), other.demote_rational()) {
+            (Number::Real(a), Number::Real(b)) => a == b,
+            (Number::Complex(a), Number::Complex(b)) => a == b,
+            (
+                Number::Measured {
+                    value: a,
+                    uncertainty: sa,
+                },
+                Number::Measured {
+                    value: b,
+                    uncertainty: sb,
+                },
+            ) => a == b && sa == sb,
+            (Number::Interval { lo: al, hi: ah }, Number::Interval { lo: bl, hi: bh }) => {
+                al == bl && ah == bh
+            }
+            _ => false,
+        }
+    }
+}
 
 impl Eq for Number {}
 
+impl std::hash::Hash for Number {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        // Make sure that 0.0 and -0.0 (which compare equal) have the same hash
+        let normalize = |n: f64| if n == 0.0 { 0.0 } else { n };
+        match self.demote_rational() {
+            Number::Real(n) => normalize(n).to_bits().hash(state),
+            Number::Complex(c) => {
+                normalize(c.re).to_bits().hash(state);
+                normalize(c.im).to_bits().hash(state);
+            }
+            Number::Measured { value, uncertainty } => {
+                normalize(value).to_bits().hash(state);
+                normalize(uncertainty).to_bits().hash(state);
+            }
+            Number::Interval { lo, hi } => {
+                normalize(lo).to_bits().hash(state);
+                normalize(hi).to_bits().hash(state);
+            }
+            Number::Rational(_) => unreachable!("rational numbers have been converted"),
+        }
+    }
+}
+
+impl PartialOrd for Number {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        match (self, other) {
+            (Number::Real(a), Number::Real(b)) => a.partial_cmp(b),
+            (Number::Rational(_), _) | (_, Number::Rational(_)) => {
+                self.demote_rational().partial_cmp(&other.demote_rational())
+            }
+            _ if self == other => Some(std::cmp::Ordering::Equal),
+            (Number::Complex(_), _) | (_, Number::Complex(_)) => None,
+            // Intervals are only ordered if they do not overlap
+            (Number::Interval { .. }, _) | (_, Number::Interval { .. }) => {
+                let ((a_lo, a_hi), (b_lo, b_hi)) = (self.bounds(), other.bounds());
+                if a_hi < b_lo {
+                    Some(std::cmp::Ordering::Less)
+                } else if a_lo > b_hi {
+                    Some(std::cmp::Ordering::Greater)
+                } else {
+                    None
+                }
+            }
+            // Measured values are ordered by their central value
+            (a, b) => match a.to_f64().partial_cmp(&b.to_f64()) {
+                Some(std::cmp::Ordering::Equal) => None,
+                ordering => ordering,
+            },
+        }
+    }
+}
+
 impl Number {
     pub fn from_f64(n: f64) -> Self {
-        Number(n)
+        Number::Real(n)
     }
 
+    pub fn from_complex(c: Complex64) -> Self {
+        if c.im == 0.0 {
+            Number::Real(c.re)
+        } else {
+            Number::Complex(c)
+        }
+    }
+
+    pub fn from_measurement(value: f64, uncertainty: f64) -> Self {
+        Number::Measured {
+            value,
+            uncertainty: uncertainty.abs(),
+        }
+    }
+
+    /// Creates an exact rational number, or a `Real` if it does not fit into 64 bits.
+    pub fn from_rational(r: Rational) -> Self {
+        match (i64::try_from(*r.numer()), i64::try_from(*r.denom())) {
+            (Ok(numer), Ok(denom)) => Number::Rational(Ratio::new_raw(numer, denom)),
+            _ => Number::Real(r.to_f64().unwrap_or(f64::NAN)),
+        }
+    }
+
+    /// Computes `base^exp` exactly, if the result can be represented as a rational number.
+    pub fn from_integer_power(base: i128, exp: i32) -> Self {
+        Number::from_rational(Rational::from_integer(base))
+            .pow(&Number::from_rational(Rational::from_integer(exp.into())))
+    }
+
+    pub fn from_interval(lo: f64, hi: f64) -> Self {
+        Number::Interval {
+            lo: lo.min(hi),
+            hi: lo.max(hi),
+        }
+    }
+
+    /// Returns the value as a real number. Complex numbers map to NaN, measured
+    /// values to their central value and intervals to their midpoint.
     pub fn to_f64(self) -> f64 {
-        let Number(n) = self;
-        n
+        match self {
+            Number::Real(n) => n,
+            Number::Complex(_) => f64::NAN,
+            Number::Measured { value, .. } => value,
+            Number::Interval { lo, hi } => lo + (hi - lo) / 2.0,
+            Number::Rational(r) => r.to_f64().unwrap_or(f64::NAN),
+        }
+    }
+
+    /// Returns the exact value of a rational number.
+    pub fn to_rational(self) -> Option<Rational> {
+        match self {
+            Number::Rational(r) => {
+                Some(Rational::new_raw((*r.numer()).into(), (*r.denom()).into()))
+            }
+            _ => None,
+        }
+    }
+
+    /// Converts rational numbers to `Real`s, leaving all other numbers unchanged.
+    pub(crate) fn demote_rational(self) -> Self {
+        match self {
+            Number::Rational(_) => Number::Real(self.to_f64()),
+            _ => self,
+        }
+    }
+
+    pub fn to_complex(self) -> Complex64 {
+        match self {
+            Number::Real(n) => Complex64::new(n, 0.0),
+            Number::Complex(c) => c,
+            Number::Measured { value, .. } => Complex64::new(value, 0.0),
+            Number::Interval { .. } => Complex64::new(f64::NAN, f64::NAN),
+            Number::Rational(_) => Complex64::new(self.to_f64(), 0.0),
+        }
+    }
+
+    /// Returns the central value and the uncertainty, which is zero for exact numbers.
+    fn value_and_uncertainty(self) -> (f64, f64) {
+        match self {
+            Number::Real(n) => (n, 0.0),
+            Number::Complex(_) => (f64::NAN, f64::NAN),
+            Number::Measured { value, uncertainty } => (value, uncertainty),
+            Number::Interval { .. } => (f64::NAN, f64::NAN),
+            Number::Rational(_) => (self.to_f64(), 0.0),
+        }
+    }
+
+    /// Returns the bounds of an interval. Real numbers are degenerate intervals.
+    fn bounds(self) -> (f64, f64) {
+        match self {
+            Number::Real(n) => (n, n),
+            Number::Rational(_) => (self.to_f64(), self.to_f64()),
+            Number::Interval { lo, hi } => (lo, hi),
+            Number::Complex(_) | Number::Measured { .. } => (f64::NAN, f64::NAN),
+        }
+    }
+
+    /// Complex numbers, measured values and intervals can be combined with real
+    /// numbers and with themselves, but not with each other.
+    fn are_incompatible(a: Number, b: Number) -> bool {
+        !matches!(a, Number::Real(_) | Number::Rational(_))
+            && !matches!(b, Number::Real(_) | Number::Rational(_))
+            && std::mem::discriminant(&a) != std::mem::discriminant(&b)
+    }
+
+    pub fn is_complex(self) -> bool {
+        matches!(self, Number::Complex(_))
+    }
+
+    pub fn is_finite(self) -> bool {
+        match self {
+            Number::Real(n) => n.is_finite(),
+            Number::Complex(c) => c.is_finite(),
+            Number::Measured { value, uncertainty } => value.is_finite() && uncertainty.is_finite(),
+            Number::Interval { lo, hi } => lo.is_finite() && hi.is_finite(),
+            Number::Rational(_) => true,
+        }
+    }
+
+    pub fn is_nan(self) -> bool {
+        match self {
+            Number::Real(n) => n.is_nan(),
+            Number::Complex(c) => c.is_nan(),
+            Number::Measured { value, uncertainty } => value.is_nan() || uncertainty.is_nan(),
+            Number::Interval { lo, hi } => lo.is_nan() || hi.is_nan(),
+            Number::Rational(_) => false,
+        }
+    }
+
+    pub fn is_infinite(self) -> bool {
+        match self {
+            Number::Real(n) => n.is_infinite(),
+            Number::Complex(c) => c.is_infinite(),
+            Number::Measured { value, uncertainty } => {
+                value.is_infinite() || uncertainty.is_infinite()
+            }
+            Number::Interval { lo, hi } => lo.is_infinite() || hi.is_infinite(),
+            Number::Rational(_) => false,
+        }
     }
 
+    /// The absolute value (modulus, for complex numbers).
+    pub fn abs(self) -> Self {
+        match self {
+            Number::Real(n) => Number::Real(n.abs()),
+            Number::Complex(c) => Number::Real(c.norm()),
+            Number::Measured { value, uncertainty } => Number::Measured {
+                value: value.abs(),
+                uncertainty,
+            },
+            Number::Interval { lo, .. } if lo >= 0.0 => self,
+            Number::Interval { lo, hi } if hi <= 0.0 => Number::Interval { lo: -hi, hi: -lo },
+            Number::Interval { lo, hi } => Number::Interval {
+                lo: 0.0,
+                hi: hi.max(-lo),
+            },
+            Number::Rational(r) if r < Ratio::zero() => -self,
+            Number::Rational(_) => self,
+        }
+    }
+
+    /// The square root. Negative real numbers yield a complex result.
+    pub fn sqrt(self) -> Self {
+        match self {
+            Number::Real(n) if n >= 0.0 || n.is_nan() => Number::Real(n.sqrt()),
+            Number::Measured { .. } | Number::Interval { .. } => self.pow(&Number::from_f64(0.5)),
+            Number::Rational(_) => self.demote_rational().sqrt(),
+            _ => Number::from_complex(self.to_complex().sqrt()),
+        }
+    }
+
+    /// The cube root. In contrast to [`Number::pow`], the cube root of a negative
+    /// real number is real (`cbrt(-8) = -2`). Complex numbers yield the principal value.
+    pub fn cbrt(self) -> Self {
+        match self {
+            Number::Real(n) => Number::Real(n.cbrt()),
+            Number::Rational(_) => self.demote_rational().cbrt(),
+            Number::Complex(c) => Number::from_complex(c.cbrt()),
+            Number::Interval { lo, hi } => {
+                // The cube root is monotonic, but `cbrt` is not correctly rounded
+                Number::from_interval(next_down(lo.cbrt()), next_up(hi.cbrt()))
+            }
+            Number::Measured { value, .. } if value < 0.0 => -(-self).cbrt(),
+            Number::Measured { .. } => self.pow(&Number::from_rational(Rational::new(1, 3))),
+        }
+    }
+
+    /// Raises `self` to the power of `other`. Negative real numbers raised to a
+    /// non-integer power yield a complex result (the principal value).
     pub fn pow(self, other: &Number) -> Self {
-        Number::from_f64(self.to_f64().pow(other.to_f64()))
+        if let Number::Rational(base) = self {
+            let exp = match *other {
+                Number::Rational(exp) if exp.is_integer() => integer_exponent(exp.to_integer()),
+                Number::Real(exp) if exp.fract() == 0.0 => integer_exponent(exp as i64),
+                _ => None,
+            };
+            let power = exp.and_then(|(n, negative)| {
+                let power = num_traits::checked_pow(base, n)?;
+                match negative {
+                    true if power.is_zero() => None,
+                    true => Some(power.recip()),
+                    false => Some(power),
+                }
+            });
+            if let Some(power) = power {
+                return Number::Rational(power);
+            }
+        }
+
+        match (self.demote_rational(), other.demote_rational()) {
+            (Number::Real(base), Number::Real(exp))
+                if base < 0.0 && exp.is_finite() && exp.fract() != 0.0 =>
+            {
+                // (-b)^e = b^e · e^(iπe). Multiples of π/2 are handled exactly to
+                // avoid tiny spurious real parts, e.g. for sqrt(-1).
+                let phase = match exp.rem_euclid(2.0) {
+                    0.5 => Complex64::i(),
+                    1.5 => -Complex64::i(),
+                    t => Complex64::from_polar(1.0, std::f64::consts::PI * t),
+                };
+                Number::from_complex(phase * (-base).powf(exp))
+            }
+            (Number::Real(base), Number::Real(exp)) => Number::Real(base.pow(exp)),
+            (base, exp) if Self::are_incompatible(base, exp) => Number::Real(f64::NAN),
+            (base @ Number::Complex(_), exp) | (base, exp @ Number::Complex(_)) => {
+                Number::from_complex(base.to_complex().powc(exp.to_complex()))
+            }
+            (Number::Interval { lo, hi }, Number::Real(exp)) => {
+                // Powers are monotonic on each side of zero, so the extrema are found
+                // at the bounds, or at zero if it lies inside of the interval. Zero is
+                // approached from both sides there, which matters for negative exponents
+                // (`[-1, 1]^-1` is unbounded in both directions), so both signed zeros
+                // are taken into account.
+                if lo < 0.0 && exp.fract() != 0.0 {
+                    return Number::Real(f64::NAN);
+                }
+                let hi_bound = if lo < 0.0 && hi == 0.0 { -0.0 } else { hi };
+                let mut candidates = vec![lo.powf(exp), hi_bound.powf(exp)];
+                if lo < 0.0 && hi > 0.0 {
+                    candidates.push((-0.0f64).powf(exp));
+                    candidates.push(0.0f64.powf(exp));
+                }
+                let lo = candidates.iter().copied().fold(f64::INFINITY, f64::min);
+                let hi = candidates.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+                // `powf` is not correctly rounded, so widen the result by one ulp
+                Number::from_interval(next_down(lo), next_up(hi))
+            }
+            (Number::Interval { .. }, _) | (_, Number::Interval { .. }) => Number::Real(f64::NAN),
+            (base, exp) => {
+                let ((a, sigma_a), (c, sigma_c)) =
+                    (base.value_and_uncertainty(), exp.value_and_uncertainty());
+                let value = a.powf(c);
+                let exponent_term = if sigma_c == 0.0 {
+                    0.0
+                } else {
+                    value * a.ln() * sigma_c
+                };
+                Number::from_measurement(
+                    value,
+                    (c * a.powf(c - 1.0) * sigma_a).hypot(exponent_term),
+                )
+            }
+        }
     }
 
-    fn is_integer(self) -> bool {
-        self.0.trunc() == self.0
+    fn binary_op(
+        self,
+        rhs: Self,
+        real: impl Fn(f64, f64) -> f64,
+        complex: impl Fn(Complex64, Complex64) -> Complex64,
+        measured: impl Fn((f64, f64), (f64, f64)) -> (f64, f64),
+        interval: impl Fn((f64, f64), (f64, f64)) -> Option<(f64, f64)>,
+        rational: impl Fn(&Ratio<i64>, &Ratio<i64>) -> Option<Ratio<i64>>,
+    ) -> Self {
+        if let (Number::Rational(a), Number::Rational(b)) = (self, rhs) {
+            if let Some(result) = rational(&a, &b) {
+                return Number::Rational(result);
+            }
+        }
+
+        let (lhs, rhs) = (self.demote_rational(), rhs.demote_rational());
+        match (lhs, rhs) {
+            (Number::Real(a), Number::Real(b)) => Number::Real(real(a, b)),
+            (a, b) if Self::are_incompatible(a, b) => Number::Real(f64::NAN),
+            (Number::Complex(_), _) | (_, Number::Complex(_)) => {
+                Number::from_complex(complex(lhs.to_complex(), rhs.to_complex()))
+            }
+            (Number::Interval { .. }, _) | (_, Number::Interval { .. }) => {
+                match interval(lhs.bounds(), rhs.bounds()) {
+                    Some((lo, hi)) => Number::Interval { lo, hi },
+                    None => Number::Real(f64::NAN),
+                }
+            }
+            (a, b) => {
+                let (value, uncertainty) =
+                    measured(a.value_and_uncertainty(), b.value_and_uncertainty());
+                Number::from_measurement(value, uncertainty)
+            }
+        }
     }
 
     pub fn pretty_print(self) -> String {
-        let number = self.0;
+        match self {
+            Number::Real(n) => Self::pretty_print_real(n),
+            Number::Rational(_) => Self::pretty_print_real(self.to_f64()),
+            Number::Complex(c) => {
+                let imaginary = match c.im.abs() {
+                    1.0 => "i".to_string(),
+                    im => format!("{}i", Self::pretty_print_real(im)),
+                };
+                match (c.re == 0.0, c.im < 0.0) {
+                    (true, false) => imaginary,
+                    (true, true) => format!("-{imaginary}"),
+                    (false, false) => format!("{} + {imaginary}", Self::pretty_print_real(c.re)),
+                    (false, true) => format!("{} - {imaginary}", Self::pretty_print_real(c.re)),
+                }
+            }
+            Number::Measured { value, uncertainty }
+                if uncertainty > 0.0 && uncertainty.is_finite() && value.is_finite() =>
+            {
+                // Round the uncertainty to one significant digit and show the value
+                // with the same number of decimals, e.g. `1.50 ± 0.02`.
+                let decimals = (-uncertainty.log10().floor()).max(0.0) as usize;
+                format!("{value:.decimals$} ± {uncertainty:.decimals$}")
+            }
+            Number::Measured { value, uncertainty } => format!(
+                "{} ± {}",
+                Self::pretty_print_real(value),
+                Self::pretty_print_real(uncertainty)
+            ),
+            Number::Interval { lo, hi } => format!(
+                "[{}, {}]",
+                Self::pretty_print_real(lo),
+                Self::pretty_print_real(hi)
+            ),
+        }
+    }
+
+    fn pretty_print_real(number: f64) -> String {
+        let is_integer = number.trunc() == number;
 
         // 64-bit floats can accurately represent integers up to 2^52 [1],
         // which is approximately 4.5 × 10^15.
         //
         // [1] https://stackoverflow.com/a/43656339
         //
-        if self.is_integer() && self.0.abs() < 1e15 {
+        if is_integer && number.abs() < 1e15 {
             use num_format::{CustomFormat, Grouping, ToFormattedString};
 
             let format = CustomFormat::builder()
-                .grouping(if self.0.abs() >= 100_000.0 {
+                .grouping(if number.abs() >= 100_000.0 {
                     Grouping::Standard
                 } else {
                     Grouping::Posix
@@ -77,11 +526,110 @@ impl Number {
     }
 }
 
+/// Splits an integer exponent into its magnitude and whether it is negative.
+fn integer_exponent(n: i64) -> Option<(usize, bool)> {
+    usize::try_from(n.unsigned_abs())
+        .ok()
+        .map(|magnitude| (magnitude, n < 0))
+}
+
+fn next_up(x: f64) -> f64 {
+    if x.is_nan() || x == f64::INFINITY {
+        x
+    } else if x == 0.0 {
+        f64::from_bits(1)
+    } else if x > 0.0 {
+        f64::from_bits(x.to_bits() + 1)
+    } else {
+        f64::from_bits(x.to_bits() - 1)
+    }
+}
+
+fn next_down(x: f64) -> f64 {
+    -next_up(-x)
+}
+
+/// Rounds a result down if the exact result is `x + error` with a negative error.
+fn round_down(x: f64, error: f64) -> f64 {
+    if error < 0.0 {
+        next_down(x)
+    } else {
+        x
+    }
+}
+
+/// Rounds a result up if the exact result is `x + error` with a positive error.
+fn round_up(x: f64, error: f64) -> f64 {
+    if error > 0.0 {
+        next_up(x)
+    } else {
+        x
+    }
+}
+
+/// Computes `a + b` and the rounding error (Knuth's TwoSum).
+fn two_sum(a: f64, b: f64) -> (f64, f64) {
+    let s = a + b;
+    let b_virtual = s - a;
+    (s, (a - (s - b_virtual)) + (b - b_virtual))
+}
+
+fn add_down(a: f64, b: f64) -> f64 {
+    let (s, error) = two_sum(a, b);
+    round_down(s, error)
+}
+
+fn add_up(a: f64, b: f64) -> f64 {
+    let (s, error) = two_sum(a, b);
+    round_up(s, error)
+}
+
+fn interval_mul((al, ah): (f64, f64), (bl, bh): (f64, f64)) -> Option<(f64, f64)> {
+    let products = [(al, bl), (al, bh), (ah, bl), (ah, bh)].map(|(a, b)| {
+        let p = a * b;
+        let error = a.mul_add(b, -p);
+        (round_down(p, error), round_up(p, error))
+    });
+    Some((
+        products.iter().map(|p| p.0).fold(f64::INFINITY, f64::min),
+        products
+            .iter()
+            .map(|p| p.1)
+            .fold(f64::NEG_INFINITY, f64::max),
+    ))
+}
+
+fn interval_div((al, ah): (f64, f64), (bl, bh): (f64, f64)) -> Option<(f64, f64)> {
+    if bl <= 0.0 && bh >= 0.0 {
+        return Some((f64::NEG_INFINITY, f64::INFINITY));
+    }
+    let quotients = [(al, bl), (al, bh), (ah, bl), (ah, bh)].map(|(a, b)| {
+        let q = a / b;
+        // The exact quotient is `q + r / b`
+        let r = (-q).mul_add(b, a);
+        (round_down(q, r / b), round_up(q, r / b))
+    });
+    Some((
+        quotients.iter().map(|q| q.0).fold(f64::INFINITY, f64::min),
+        quotients
+            .iter()
+            .map(|q| q.1)
+            .fold(f64::NEG_INFINITY, f64::max),
+    ))
+}
+
 impl std::ops::Add for Number {
     type Output = Number;
 
     fn add(self, rhs: Self) -> Self::Output {
-        Number(self.0 + rhs.0)
+        self.binary_op(
+            rhs,
+            |a, b| a + b,
+            |a, b| a + b,
+            |(a, sa), (b, sb)| (a + b, sa.hypot(sb)),
+            |(al, ah), (bl, bh)| Some((add_down(al, bl), add_up(ah, bh))),
+            CheckedAdd::checked_add,
+        )
     }
 }
 
@@ -89,7 +637,14 @@ impl std::ops::Sub for Number {
     type Output = Number;
 
     fn sub(self, rhs: Self) -> Self::Output {
-        Number(self.0 - rhs.0)
+        self.binary_op(
+            rhs,
+            |a, b| a - b,
+            |a, b| a - b,
+            |(a, sa), (b, sb)| (a - b, sa.hypot(sb)),
+            |(al, ah), (bl, bh)| Some((add_down(al, -bh), add_up(ah, -bl))),
+            CheckedSub::checked_sub,
+        )
     }
 }
 
@@ -97,7 +652,14 @@ impl std::ops::Mul for Number {
     type Output = Number;
 
     fn mul(self, rhs: Self) -> Self::Output {
-        Number(self.0 * rhs.0)
+        self.binary_op(
+            rhs,
+            |a, b| a * b,
+            |a, b| a * b,
+            |(a, sa), (b, sb)| (a * b, (b * sa).hypot(a * sb)),
+            interval_mul,
+            CheckedMul::checked_mul,
+        )
     }
 }
 
@@ -105,7 +667,32 @@ impl std::ops::Div for Number {
     type Output = Number;
 
     fn div(self, rhs: Self) -> Self::Output {
-        Number(self.0 / rhs.0)
+        self.binary_op(
+            rhs,
+            |a, b| a / b,
+            |a, b| a / b,
+            |(a, sa), (b, sb)| (a / b, (sa / b).hypot(a * sb / (b * b))),
+            interval_div,
+            CheckedDiv::checked_div,
+        )
+    }
+}
+
+impl std::ops::Rem for Number {
+    type Output = Number;
+
+    fn rem(self, rhs: Self) -> Self::Output {
+        self.binary_op(
+            rhs,
+            |a, b| a % b,
+            |a, b| a % b,
+            |(a, sa), (b, sb)| (a % b, sa.hypot((a / b).trunc() * sb)),
+            |_, _| None,
+            |a, b| {
+                let quotient = a.checked_div(b)?.trunc();
+                a.checked_sub(&b.checked_mul(&quotient)?)
+            },
+        )
     }
 }
 
@@ -113,13 +700,55 @@ impl std::ops::Neg for Number {
     type Output = Number;
 
     fn neg(self) -> Self::Output {
-        Number(-self.0)
+        match self {
+            Number::Real(n) => Number::Real(-n),
+            Number::Complex(c) => Number::Complex(-c),
+            Number::Measured { value, uncertainty } => Number::Measured {
+                value: -value,
+                uncertainty,
+            },
+            Number::Interval { lo, hi } => Number::Interval { lo: -hi, hi: -lo },
+            Number::Rational(r) => Ratio::zero()
+                .checked_sub(&r)
+                .map(Number::Rational)
+                .unwrap_or(Number::Real(-self.to_f64())),
+        }
     }
 }
 
 impl std::iter::Product for Number {
     fn product<I: Iterator<Item = Self>>(iter: I) -> Self {
-        iter.fold(Number::from_f64(1.0), |acc, n| acc * n)
+        iter.fold(Number::Rational(Ratio::one()), |acc, n| acc * n)
+    }
+}
+
+impl std::str::FromStr for Number {
+    type Err = ParseNumberError;
+
+    /// Parses a decimal number with an optional sign and exponent, like `-6.022e23`.
+    /// Underscores can be used as digit separators (`1_000_000`), but only between
+    /// two digits.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if s.is_empty() {
+            return Err(ParseNumberError::Empty);
+        }
+
+        let chars: Vec<char> = s.chars().collect();
+        for (i, &c) in chars.iter().enumerate() {
+            let is_separator = c == '_'
+                && i > 0
+                && chars[i - 1].is_ascii_digit()
+                && chars.get(i + 1).is_some_and(|c| c.is_ascii_digit());
+            if !(c.is_ascii_digit() || matches!(c, '.' | 'e' | 'E' | '+' | '-') || is_separator) {
+                return Err(ParseNumberError::UnexpectedCharacter(c));
+            }
+        }
+
+        s.replace('_', "")
+            .parse::<f64>()
+            .map(Number::from_f64)
+            .map_err(|_| ParseNumberError::Invalid(s.into()))
     }
 }
 
@@ -158,3 +787,230 @@ fn test_pretty_print() {
     assert_eq!(Number::from_f64(0.000001).pretty_print(), "0.000001");
     assert_eq!(Number::from_f64(0.0000001).pretty_print(), "1.0e-7");
 }
+
+#[test]
+fn test_complex() {
+    let i = Number::from_f64(-1.0).pow(&Number::from_f64(0.5));
+    assert_eq!(i, Number::Complex(Complex64::i()));
+    assert_eq!(i * i, Number::from_f64(-1.0));
+    assert_eq!(i.abs(), Number::from_f64(1.0));
+    assert!(i.to_f64().is_nan());
+    assert_eq!(i.partial_cmp(&Number::from_f64(1.0)), None);
+
+    assert_eq!(i.pretty_print(), "i");
+    assert_eq!((-i).pretty_print(), "-i");
+    assert_eq!(
+        (Number::from_f64(1.0) + Number::from_f64(2.5) * i).pretty_print(),
+        "1 + 2.5i"
+    );
+    assert_eq!((Number::from_f64(1.0) - i).pretty_print(), "1 - i");
+}
+
+#[test]
+fn test_real_arithmetic_unchanged() {
+    let values = [0.1, -0.3, 7.0, 1e300, -2.5e-10];
+    for a in values {
+        for b in values {
+            let (x, y) = (Number::from_f64(a), Number::from_f64(b));
+            assert_eq!((x + y).to_f64().to_bits(), (a + b).to_bits());
+            assert_eq!((x - y).to_f64().to_bits(), (a - b).to_bits());
+            assert_eq!((x * y).to_f64().to_bits(), (a * b).to_bits());
+            assert_eq!((x / y).to_f64().to_bits(), (a / b).to_bits());
+            if a >= 0.0 {
+                assert_eq!(x.pow(&y).to_f64().to_bits(), a.powf(b).to_bits());
+            }
+        }
+    }
+    assert_eq!(
+        Number::from_f64(-2.0).pow(&Number::from_f64(3.0)),
+        Number::from_f64(-8.0)
+    );
+}
+
+#[test]
+fn test_measured() {
+    let a = Number::from_measurement(10.0, 1.0);
+    let b = Number::from_measurement(5.0, 1.0);
+
+    assert_eq!(a + b, Number::from_measurement(15.0, 2.0f64.sqrt()));
+    assert_eq!(a - b, Number::from_measurement(5.0, 2.0f64.sqrt()));
+    assert_eq!(
+        a * Number::from_f64(3.0),
+        Number::from_measurement(30.0, 3.0)
+    );
+    assert_eq!(a * b, Number::from_measurement(50.0, 125.0f64.sqrt()));
+    assert_eq!(
+        a / Number::from_f64(2.0),
+        Number::from_measurement(5.0, 0.5)
+    );
+    assert_eq!(
+        a.pow(&Number::from_f64(2.0)),
+        Number::from_measurement(100.0, 20.0)
+    );
+    assert_eq!(-a, Number::from_measurement(-10.0, 1.0));
+
+    assert_eq!(
+        Number::from_measurement(1.5, 0.02).pretty_print(),
+        "1.50 ± 0.02"
+    );
+    assert_eq!(
+        Number::from_measurement(1234.5, 30.0).pretty_print(),
+        "1234 ± 30"
+    );
+    assert_eq!(Number::from_measurement(2.0, 0.0).pretty_print(), "2 ± 0");
+
+    assert!((a * Number::Complex(Complex64::i())).is_nan());
+}
+
+#[test]
+fn test_interval() {
+    let a = Number::from_interval(1.0, 2.0);
+    let b = Number::from_interval(3.0, 4.0);
+
+    assert_eq!(a + b, Number::from_interval(4.0, 6.0));
+    assert_eq!(a - b, Number::from_interval(-3.0, -1.0));
+    assert_eq!(a * b, Number::from_interval(3.0, 8.0));
+    assert_eq!(-a * b, Number::from_interval(-8.0, -3.0));
+    assert_eq!(b / a, Number::from_interval(1.5, 4.0));
+    assert_eq!(
+        a / Number::from_interval(-1.0, 1.0),
+        Number::from_interval(f64::NEG_INFINITY, f64::INFINITY)
+    );
+
+    // 0.1 + 0.2 is not representable, so the result is rounded outwards
+    let Number::Interval { lo, hi } = Number::from_interval(0.1, 0.1) + Number::from_f64(0.2)
+    else {
+        panic!("expected an interval");
+    };
+    assert!(lo < hi);
+    assert!(lo <= 0.3 && 0.3 <= hi);
+
+    assert_eq!(
+        Number::from_interval(-1.0, 1.0).pow(&Number::from_f64(-1.0)),
+        Number::from_interval(f64::NEG_INFINITY, f64::INFINITY)
+    );
+    let Number::Interval { lo, hi } = Number::from_interval(-1.0, 1.0).pow(&Number::from_f64(-2.0))
+    else {
+        panic!("expected an interval");
+    };
+    assert!(lo <= 1.0 && hi == f64::INFINITY);
+    let Number::Interval { lo, hi } = Number::from_interval(-2.0, 0.0).pow(&Number::from_f64(-1.0))
+    else {
+        panic!("expected an interval");
+    };
+    assert!(lo == f64::NEG_INFINITY && hi >= -0.5);
+
+    assert!(a < b);
+    assert_eq!(a.partial_cmp(&Number::from_interval(1.5, 3.0)), None);
+    assert_eq!(a.pretty_print(), "[1, 2]");
+    assert!((a + Number::from_measurement(1.0, 0.1)).is_nan());
+}
+
+#[test]
+fn test_cbrt() {
+    assert_eq!(Number::from_f64(8.0).cbrt(), Number::from_f64(2.0));
+    assert_eq!(Number::from_f64(-8.0).cbrt(), Number::from_f64(-2.0));
+    assert_eq!(
+        Number::from_rational(Rational::new(27, 8)).cbrt(),
+        Number::from_f64(1.5)
+    );
+
+    let Number::Complex(c) = Number::Complex(Complex64::new(-8.0, 0.0)).cbrt() else {
+        panic!("expected a complex number");
+    };
+    assert!((c - Complex64::new(1.0, 3.0f64.sqrt())).norm() < 1e-12);
+
+    let Number::Measured { value, uncertainty } = Number::from_measurement(-8.0, 1.2).cbrt() else {
+        panic!("expected a measured value");
+    };
+    assert!((value - -2.0).abs() < 1e-12);
+    assert!((uncertainty - 0.1).abs() < 1e-12);
+
+    let Number::Interval { lo, hi } = Number::from_interval(-8.0, 27.0).cbrt() else {
+        panic!("expected an interval");
+    };
+    assert!(lo <= -2.0 && -2.0 - lo < 1e-12);
+    assert!(hi >= 3.0 && hi - 3.0 < 1e-12);
+}
+
+#[test]
+fn test_rational() {
+    let r = |n: i128, d: i128| Number::from_rational(Rational::new(n, d));
+
+    assert_eq!(r(1, 10) + r(2, 10), r(3, 10));
+    assert_eq!(
+        (r(1, 10) + r(2, 10)).to_rational(),
+        Some(Rational::new(3, 10))
+    );
+    assert_eq!(r(1, 3) * r(3, 1), r(1, 1));
+    assert_eq!(r(1, 3) / r(1, 6), r(2, 1));
+    assert_eq!(r(7, 2) % r(1, 1), r(1, 2));
+    assert_eq!(-r(1, 3), r(-1, 3));
+    assert_eq!(r(2, 3).pow(&r(-2, 1)), r(9, 4));
+    assert_eq!(Number::from_integer_power(10, -3), r(1, 1000));
+
+    // Mixing with other kinds of numbers falls back to floating point
+    assert_eq!(r(1, 2) + Number::from_f64(0.25), Number::from_f64(0.75));
+    assert_eq!(r(1, 2), Number::from_f64(0.5));
+    assert_eq!(r(1, 4).sqrt(), Number::from_f64(0.5));
+
+    // Equality is transitive, also across kinds of numbers
+    let third = r(1, 3);
+    let almost_third = r(333_333_333_333_333_333, 1_000_000_000_000_000_000);
+    assert_eq!(third, Number::from_f64(1.0 / 3.0));
+    assert_eq!(Number::from_f64(1.0 / 3.0), almost_third);
+    assert_eq!(third, almost_third);
+    assert_eq!(
+        third.partial_cmp(&almost_third),
+        Some(std::cmp::Ordering::Equal)
+    );
+
+    // Results that do not fit into 64 bits fall back to floating point
+    let large = Number::from_integer_power(10, 30);
+    assert_eq!(large.to_rational(), None);
+    assert_eq!(large, Number::from_f64(1e30));
+    assert_eq!(
+        Number::from_integer_power(10, 18) * r(100, 1),
+        Number::from_f64(1e20)
+    );
+
+    assert_eq!(r(1, 2).pretty_print(), "0.5");
+}
+
+#[test]
+fn test_from_str() {
+    use std::str::FromStr;
+
+    assert_eq!(Number::from_str("6.022e23"), Ok(Number::from_f64(6.022e23)));
+    assert_eq!(Number::from_str("1E-3"), Ok(Number::from_f64(1e-3)));
+    assert_eq!(Number::from_str("1_000"), Ok(Number::from_f64(1000.0)));
+    assert_eq!(
+        Number::from_str("1_000.000_1"),
+        Ok(Number::from_f64(1000.0001))
+    );
+    assert_eq!(Number::from_str("+2.5"), Ok(Number::from_f64(2.5)));
+    assert_eq!(Number::from_str("-2.5"), Ok(Number::from_f64(-2.5)));
+    assert_eq!(Number::from_str(" 42 "), Ok(Number::from_f64(42.0)));
+
+    assert_eq!(
+        Number::from_str("1.2.3"),
+        Err(ParseNumberError::Invalid("1.2.3".into()))
+    );
+    assert_eq!(Number::from_str(""), Err(ParseNumberError::Empty));
+    assert_eq!(
+        Number::from_str("_1"),
+        Err(ParseNumberError::UnexpectedCharacter('_'))
+    );
+    assert_eq!(
+        Number::from_str("1__0"),
+        Err(ParseNumberError::UnexpectedCharacter('_'))
+    );
+    assert_eq!(
+        Number::from_str("inf"),
+        Err(ParseNumberError::UnexpectedCharacter('i'))
+    );
+    assert_eq!(
+        "1e".parse::<Number>(),
+        Err(ParseNumberError::Invalid("1e".into()))
+    );
+}
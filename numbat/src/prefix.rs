@@ -1,18 +1,30 @@
-use crate::number::Number;
+use std::{fmt::Display, str::FromStr};
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+use thiserror::Error;
+
+use crate::{number::Number, prefix_parser::PrefixParser};
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum Prefix {
     /// Represents a metric/decimal prefix symbolizing 10^n
     Metric(i32),
     /// Represents a binary prefix symbolizing 2^n
     Binary(i32),
+    /// Represents a user-defined prefix with an arbitrary factor
+    Custom {
+        short: String,
+        long: String,
+        factor: Number,
+    },
 }
 
 impl Prefix {
     pub fn factor(&self) -> Number {
         match self {
-            Prefix::Metric(exp) => Number::from_f64(10.0f64.powi(*exp)),
-            Prefix::Binary(exp) => Number::from_f64(2.0f64.powi(*exp)),
+            Prefix::Metric(exp) => Number::from_integer_power(10, *exp),
+            Prefix::Binary(exp) => Number::from_integer_power(2, *exp),
+            Prefix::Custom { factor, .. } => *factor,
         }
     }
 
@@ -20,6 +32,16 @@ impl Prefix {
         Prefix::Metric(0)
     }
 
+    #[cfg(test)]
+    pub fn quecto() -> Self {
+        Prefix::Metric(-30)
+    }
+
+    #[cfg(test)]
+    pub fn ronto() -> Self {
+        Prefix::Metric(-27)
+    }
+
     #[cfg(test)]
     pub fn micro() -> Self {
         Prefix::Metric(-6)
@@ -70,6 +92,16 @@ impl Prefix {
         Prefix::Metric(12)
     }
 
+    #[cfg(test)]
+    pub fn ronna() -> Self {
+        Prefix::Metric(27)
+    }
+
+    #[cfg(test)]
+    pub fn quetta() -> Self {
+        Prefix::Metric(30)
+    }
+
     #[cfg(test)]
     pub fn kibi() -> Self {
         Prefix::Binary(10)
@@ -85,19 +117,43 @@ impl Prefix {
         Prefix::Binary(30)
     }
 
+    /// Returns the metric prefix for `10^e`. Only exponents that are multiples of three
+    /// (from `quecto` to `quetta`) are mapped, so `centi`, `deci`, `deca` and `hecto`
+    /// are never returned.
+    pub fn from_exponent(e: i32) -> Option<Prefix> {
+        if e % 3 == 0 && (-30..=30).contains(&e) {
+            Some(Prefix::Metric(e))
+        } else {
+            None
+        }
+    }
+
+    /// Returns the power-of-ten exponent of a metric prefix, or `None` for binary prefixes.
+    pub fn to_exponent(&self) -> Option<i32> {
+        match self {
+            Prefix::Metric(exp) => Some(*exp),
+            Prefix::Binary(_) | Prefix::Custom { .. } => None,
+        }
+    }
+
+    /// Whether this prefix has no effect, i.e. `10^0` or `2^0`.
     pub fn is_none(&self) -> bool {
         match self {
             Prefix::Metric(0) => true,
             Prefix::Binary(0) => true,
             Prefix::Metric(_) => false,
             Prefix::Binary(_) => false,
+            Prefix::Custom { .. } => false,
         }
     }
 
+    /// Whether this is a metric prefix like `kilo` (`10^3`). Note that
+    /// [`Prefix::none`] is a metric prefix as well.
     pub fn is_metric(&self) -> bool {
         matches!(self, Prefix::Metric(_))
     }
 
+    /// Whether this is a binary prefix like `kibi` (`2^10`).
     pub fn is_binary(&self) -> bool {
         matches!(self, Prefix::Binary(_))
     }
@@ -143,6 +199,8 @@ impl Prefix {
             Prefix::Binary(80) => "Yi".into(),
 
             Prefix::Binary(n) => format!("<prefix 2^{}>", n),
+
+            Prefix::Custom { short, .. } => short.clone(),
         }
     }
 
@@ -187,6 +245,142 @@ impl Prefix {
             Prefix::Binary(80) => "yobi".into(),
 
             Prefix::Binary(n) => format!("<prefix 2^{}>", n),
+
+            Prefix::Custom { long, .. } => long.clone(),
+        }
+    }
+}
+
+/// Prints the short symbol of the prefix, like `k` or `Ki`. Parsing this symbol with
+/// [`FromStr`] yields the same prefix again (for all named, non-custom prefixes).
+impl Display for Prefix {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_string_short())
+    }
+}
+
+#[derive(Debug, Clone, Error, PartialEq, Eq)]
+#[error("Unknown prefix '{0}'")]
+pub struct UnknownPrefixError(pub String);
+
+/// Parses short symbols (`k`, `Ki`, `µ`, `u`) as well as long names (`kilo`, `kibi`,
+/// `micro`) of metric and binary prefixes. The empty string is parsed as no prefix.
+impl FromStr for Prefix {
+    type Err = UnknownPrefixError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.is_empty() {
+            return Ok(Prefix::none());
+        }
+
+        PrefixParser::prefixes()
+            .iter()
+            .find(|(long, shorts, _)| *long == s || shorts.contains(&s))
+            .map(|(_, _, prefix)| prefix.clone())
+            .ok_or_else(|| UnknownPrefixError(s.into()))
+    }
+}
+
+impl PartialOrd for Prefix {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Prefix {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        match (self, other) {
+            (Prefix::Metric(a), Prefix::Metric(b)) | (Prefix::Binary(a), Prefix::Binary(b)) => {
+                a.cmp(b)
+            }
+            (
+                Prefix::Custom {
+                    short: short_a,
+                    long: long_a,
+                    factor: factor_a,
+                },
+                Prefix::Custom {
+                    short: short_b,
+                    long: long_b,
+                    factor: factor_b,
+                },
+            ) => short_a
+                .cmp(short_b)
+                .then_with(|| long_a.cmp(long_b))
+                .then_with(|| factor_a.to_f64().total_cmp(&factor_b.to_f64())),
+            (Prefix::Metric(_), _) => std::cmp::Ordering::Less,
+            (_, Prefix::Metric(_)) => std::cmp::Ordering::Greater,
+            (Prefix::Binary(_), _) => std::cmp::Ordering::Less,
+            (_, Prefix::Binary(_)) => std::cmp::Ordering::Greater,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_exponent_to_exponent() {
+        assert_eq!(Prefix::from_exponent(-6), Some(Prefix::micro()));
+        assert_eq!(Prefix::from_exponent(30), Some(Prefix::quetta()));
+        assert_eq!(Prefix::from_exponent(0), Some(Prefix::none()));
+        assert_eq!(Prefix::from_exponent(1), None);
+        assert_eq!(Prefix::from_exponent(-2), None);
+        assert_eq!(Prefix::from_exponent(33), None);
+
+        assert_eq!(Prefix::kilo().to_exponent(), Some(3));
+        assert_eq!(Prefix::centi().to_exponent(), Some(-2));
+        assert_eq!(Prefix::kibi().to_exponent(), None);
+
+        for e in (-30..=30).step_by(3) {
+            assert_eq!(Prefix::from_exponent(e).unwrap().to_exponent(), Some(e));
+        }
+    }
+
+    #[test]
+    fn predicates() {
+        assert!(Prefix::kibi().is_binary());
+        assert!(!Prefix::kibi().is_metric());
+        assert!(!Prefix::kibi().is_none());
+
+        assert!(Prefix::kilo().is_metric());
+        assert!(!Prefix::kilo().is_binary());
+        assert!(!Prefix::kilo().is_none());
+
+        assert!(Prefix::none().is_none());
+        assert!(Prefix::none().is_metric());
+        assert!(Prefix::Binary(0).is_none());
+
+        let custom = Prefix::Custom {
+            short: "x".into(),
+            long: "extra".into(),
+            factor: Number::from_f64(3.0),
+        };
+        assert!(!custom.is_metric());
+        assert!(!custom.is_binary());
+        assert!(!custom.is_none());
+    }
+
+    #[test]
+    fn display_and_from_str() {
+        assert_eq!("k".parse(), Ok(Prefix::kilo()));
+        assert_eq!("kilo".parse(), Ok(Prefix::kilo()));
+        assert_eq!("Ki".parse(), Ok(Prefix::kibi()));
+        assert_eq!("kibi".parse(), Ok(Prefix::kibi()));
+        assert_eq!("µ".parse(), Ok(Prefix::micro()));
+        assert_eq!("μ".parse(), Ok(Prefix::micro()));
+        assert_eq!("u".parse(), Ok(Prefix::micro()));
+        assert_eq!("micro".parse(), Ok(Prefix::micro()));
+        assert_eq!("".parse(), Ok(Prefix::none()));
+        assert_eq!("x".parse::<Prefix>(), Err(UnknownPrefixError("x".into())));
+
+        assert_eq!(Prefix::micro().to_string(), "µ");
+        assert_eq!(Prefix::gibi().to_string(), "Gi");
+
+        for (_, _, prefix) in PrefixParser::prefixes() {
+            assert_eq!(prefix.to_string().parse().as_ref(), Ok(prefix));
+            assert_eq!(prefix.as_string_long().parse().as_ref(), Ok(prefix));
         }
     }
 }
@@ -16,7 +16,8 @@ pub enum PrefixParserResult {
 
 type Result<T> = std::result::Result<T, NameResolutionError>;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct AcceptsPrefix {
     pub short: bool,
     pub long: bool,
@@ -83,7 +84,7 @@ impl PrefixParser {
         }
     }
 
-    fn prefixes() -> &'static [(&'static str, &'static [&'static str], Prefix)] {
+    pub(crate) fn prefixes() -> &'static [(&'static str, &'static [&'static str], Prefix)] {
         PREFIXES.get_or_init(|| {
             vec![
                 // Metric prefixes:
@@ -254,7 +255,7 @@ impl PrefixParser {
                 {
                     return PrefixParserResult::UnitIdentifier(
                         info.definition_span,
-                        *prefix,
+                        prefix.clone(),
                         unit_name.to_string(),
                         info.full_name.clone(),
                     );
@@ -268,7 +269,7 @@ impl PrefixParser {
                 {
                     return PrefixParserResult::UnitIdentifier(
                         info.definition_span,
-                        *prefix,
+                        prefix.clone(),
                         unit_name.to_string(),
                         info.full_name.clone(),
                     );
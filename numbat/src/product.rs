@@ -1,5 +1,6 @@
 use std::{
     fmt::Display,
+    hash::Hash,
     ops::{Div, Mul},
 };
 
@@ -17,6 +18,7 @@ pub trait Canonicalize {
     fn is_trivial(&self) -> bool;
 }
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Debug, Clone)]
 pub struct Product<Factor, const CANONICALIZE: bool = false> {
     factors: Vec<Factor>,
@@ -244,6 +246,15 @@ impl<Factor: Clone + Ord + Canonicalize + Eq, const CANONICALIZE: bool> Eq
 {
 }
 
+impl<Factor: Clone + Ord + Canonicalize + Hash, const CANONICALIZE: bool> Hash
+    for Product<Factor, CANONICALIZE>
+{
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        // Consistent with the PartialEq implementation above
+        self.canonicalized().factors.hash(state)
+    }
+}
+
 impl<Factor, const CANONICALIZE: bool> IntoIterator for Product<Factor, CANONICALIZE> {
     type IntoIter = ProductIntoIter<Factor>;
     type Item = Factor;
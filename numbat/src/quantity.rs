@@ -1,7 +1,11 @@
-use crate::arithmetic::{Exponent, Power, Rational};
-use crate::number::Number;
+use crate::arithmetic::{exact_rational_from_f64, superscript_integer, Exponent, Power, Rational};
+use crate::dimension::Dimension;
+use crate::markup::{self as m, Markup};
+use crate::number::{Number, ParseNumberError};
+use crate::prefix::Prefix;
 use crate::pretty_print::PrettyPrint;
 use crate::unit::{is_multiple_of, Unit, UnitFactor};
+use crate::unit_parser::UnitParseError;
 
 use itertools::Itertools;
 use num_rational::Ratio;
@@ -11,14 +15,49 @@ use thiserror::Error;
 #[derive(Clone, Debug, Error, PartialEq, Eq)]
 pub enum QuantityError {
     #[error("Conversion error: unit '{0}' can not be converted to '{1}'")]
-    IncompatibleUnits(Unit, Unit), // TODO: this can currently be triggered if there are multiple base units for the same dimension (no way to convert between them)
+    IncompatibleUnits(Unit, Unit),
+
+    #[error("Conversion error: units '{0}' and '{1}' have the same physical dimension, but are based on different base units")]
+    AmbiguousBaseUnits(Unit, Unit),
 
     #[error("Non-rational exponent")]
     NonRationalExponent,
+
+    #[error("Division by zero")]
+    DivisionByZero,
+
+    #[error("Arithmetic overflow: the result is not finite")]
+    Overflow,
+
+    #[error("during {op}: {source}")]
+    Context {
+        op: String,
+        source: Box<QuantityError>,
+    },
+}
+
+impl QuantityError {
+    /// Wraps this error with the name of the operation that failed, like `add`.
+    pub fn with_context(self, op: &str) -> QuantityError {
+        QuantityError::Context {
+            op: op.into(),
+            source: Box::new(self),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Error, PartialEq, Eq)]
+pub enum QuantityParseError {
+    #[error(transparent)]
+    Number(#[from] ParseNumberError),
+
+    #[error(transparent)]
+    Unit(#[from] UnitParseError),
 }
 
 pub type Result<T> = std::result::Result<T, QuantityError>;
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Debug, Clone)]
 pub struct Quantity {
     value: Number,
@@ -49,79 +88,420 @@ impl Quantity {
         &self.unit
     }
 
+    /// Replaces the unit of this quantity while keeping its numeric value, i.e.
+    /// `2 m` becomes `2 s`.
+    ///
+    /// **This does not convert the value.** Use [`Quantity::convert_to`] to express
+    /// the same physical quantity in a different unit (`2 m` becomes `200 cm`).
+    pub fn with_unit(self, unit: Unit) -> Quantity {
+        Quantity::new(self.value, unit)
+    }
+
+    /// See [`Unit::numerator_denominator`].
+    pub fn numerator_denominator(&self) -> (Unit, Unit) {
+        self.unit.numerator_denominator()
+    }
+
     pub fn is_zero(&self) -> bool {
         self.value.to_f64() == 0.0
     }
 
+    pub fn is_finite(&self) -> bool {
+        self.value.is_finite()
+    }
+
+    pub fn is_nan(&self) -> bool {
+        self.value.is_nan()
+    }
+
+    pub fn is_infinite(&self) -> bool {
+        self.value.is_infinite()
+    }
+
+    /// Returns the sign of the value, independent of the unit. Follows `f64::signum`,
+    /// i.e. `+0.0` maps to `1.0`.
+    pub fn signum(&self) -> Number {
+        Number::from_f64(self.value.to_f64().signum())
+    }
+
+    pub fn is_positive(&self) -> bool {
+        self.value.to_f64() > 0.0
+    }
+
+    pub fn is_negative(&self) -> bool {
+        self.value.to_f64() < 0.0
+    }
+
+    /// Expresses this quantity in base units, i.e. with all derived units and
+    /// prefixes expanded and the value scaled accordingly.
     pub fn to_base_unit_representation(&self) -> Quantity {
         let (unit, factor) = self.unit.to_base_unit_representation();
-        Quantity::new(self.value * factor, unit)
+        match self.unit.affine_offset() {
+            Some(offset) => Quantity::new(self.value * factor + offset, unit),
+            None => Quantity::new(self.value * factor, unit),
+        }
     }
 
-    pub fn convert_to(&self, target_unit: &Unit) -> Result<Quantity> {
-        if &self.unit == target_unit || self.unsafe_value().to_f64().is_zero() {
-            Ok(Quantity::new(self.value, target_unit.clone()))
-        } else {
-            // Remove common unit factors to reduce unnecessary conversion procedures
-            // For example: when converting from km/hour to mile/hour, there is no need
-            // to also perform the hour->second conversion, which would be needed, as
-            // we go back to base units for now. Removing common factors is just one
-            // heuristic, but it would be better to solve this in a more general way.
-            // For more details on this problem, see [1].
-            //
-            // [1] https://github.com/sharkdp/numbat/issues/118.
-            let mut common_unit_factors = Unit::scalar();
-            let target_unit_canonicalized = target_unit.canonicalized();
-
-            for factor in self.unit.canonicalized().iter() {
-                if let Some(other_factor) = target_unit_canonicalized
-                    .iter()
-                    .find(|&f| factor.prefix == f.prefix && factor.unit_id == f.unit_id)
-                {
-                    if factor.exponent > Ratio::zero() && other_factor.exponent > Ratio::zero() {
-                        common_unit_factors = common_unit_factors
-                            * Unit::from_factor(UnitFactor {
-                                exponent: std::cmp::min(factor.exponent, other_factor.exponent),
-                                ..factor.clone()
-                            });
-                    } else if factor.exponent < Ratio::zero()
-                        && other_factor.exponent < Ratio::zero()
-                    {
-                        common_unit_factors = common_unit_factors
-                            * Unit::from_factor(UnitFactor {
-                                exponent: std::cmp::max(factor.exponent, other_factor.exponent),
-                                ..factor.clone()
-                            });
-                    }
-                }
+    /// Converts this quantity to SI base units, e.g. for storage or comparison. Unlike
+    /// [`Quantity::to_base_unit_representation`], which uses the gram as the unit of
+    /// mass, masses are expressed in kilograms (`1 kWh` becomes `3.6e6 kg·m²/s²`). All
+    /// other factors of the result are base units without prefixes. Affine units like
+    /// `°C` are converted to their absolute base unit (`K`).
+    pub fn to_si_base(&self) -> Quantity {
+        let base_unit_representation = self.to_base_unit_representation();
+        let si_unit = Unit::from_factors(base_unit_representation.unit.iter().map(|factor| {
+            let mut factor = factor.clone();
+            if factor.unit_id.name() == "gram" {
+                factor.prefix = Prefix::Metric(3);
             }
+            factor
+        }));
+        base_unit_representation
+            .convert_to(&si_unit)
+            .expect("the SI unit has the same dimension as the base unit")
+    }
+
+    /// Converts quantities with an affine unit (like `°C`) to base units. Other
+    /// quantities are returned unchanged.
+    fn without_affine_unit(self) -> Quantity {
+        if self.unit.affine_offset().is_some() {
+            self.to_base_unit_representation()
+        } else {
+            self
+        }
+    }
 
-            let target_unit_reduced =
-                (target_unit.clone() / common_unit_factors.clone()).canonicalized();
-            let own_unit_reduced =
-                (self.unit.clone() / common_unit_factors.clone()).canonicalized();
+    /// Whether `self` or `other` has an affine unit (like `°C`). Such quantities are
+    /// converted to base units before adding or subtracting them.
+    fn involves_affine_unit(&self, other: &Quantity) -> bool {
+        self.unit.affine_offset().is_some() || other.unit.affine_offset().is_some()
+    }
 
-            let (target_base_unit_representation, factor) =
-                target_unit_reduced.to_base_unit_representation();
+    /// Prepares the operands of a multiplication or division, see
+    /// [`Quantity::without_affine_unit`]. This is a no-op for the common case of
+    /// units without affine factors.
+    fn without_affine_units(lhs: Quantity, rhs: Quantity) -> (Quantity, Quantity) {
+        if lhs.unit.has_affine_factor() || rhs.unit.has_affine_factor() {
+            (lhs.without_affine_unit(), rhs.without_affine_unit())
+        } else {
+            (lhs, rhs)
+        }
+    }
 
-            let quantity_base_unit_representation = (self.clone()
-                / Quantity::from_unit(common_unit_factors))
-            .to_base_unit_representation();
-            let own_base_unit_representation = own_unit_reduced.to_base_unit_representation().0;
+    pub fn convert_to(&self, target_unit: &Unit) -> Result<Quantity> {
+        if &self.unit == target_unit {
+            Ok(Quantity::new(self.value, target_unit.clone()))
+        } else if self.unit.affine_offset().is_some() || target_unit.affine_offset().is_some() {
+            // Affine units can not be converted with a conversion factor alone, so we
+            // always go through the (absolute) base unit representation here.
+            let own_base_unit_representation = self.to_base_unit_representation();
+            let (target_base_unit_representation, factor) =
+                target_unit.to_base_unit_representation();
 
-            if own_base_unit_representation == target_base_unit_representation {
+            if own_base_unit_representation.unit == target_base_unit_representation {
+                let offset = target_unit.affine_offset().unwrap_or(Number::from_f64(0.0));
                 Ok(Quantity::new(
-                    *quantity_base_unit_representation.unsafe_value() / factor,
+                    (own_base_unit_representation.value - offset) / factor,
                     target_unit.clone(),
                 ))
             } else {
-                // TODO: can this even be triggered? replace by an assertion?
-                Err(QuantityError::IncompatibleUnits(
-                    self.unit.clone(),
-                    target_unit.clone(),
-                ))
+                Err(self.incompatible_units_error(target_unit))
+            }
+        } else if self.unsafe_value().to_f64().is_zero() {
+            Ok(Quantity::new(self.value, target_unit.clone()))
+        } else if let Some((own_factor, target_factor)) = self.prefix_factors(target_unit) {
+            Ok(Quantity::new(
+                self.value * own_factor / target_factor,
+                target_unit.clone(),
+            ))
+        } else {
+            self.convert_to_via_base_units(target_unit)
+        }
+    }
+
+    /// Compares two quantities after converting `other` to the unit of `self`. The
+    /// values are compared with [`f64::total_cmp`], so `NaN` has a well-defined
+    /// position (after positive infinity, unless its sign bit is set).
+    pub fn total_cmp(&self, other: &Quantity) -> Result<std::cmp::Ordering> {
+        // `convert_to` accepts zero quantities for any target unit, see `partial_cmp`
+        if other.is_zero() && !other.unit.is_scalar() && !self.unit.is_compatible_with(&other.unit)
+        {
+            return Err(other.incompatible_units_error(&self.unit));
+        }
+
+        let other_converted = other.convert_to(&self.unit)?;
+        Ok(self
+            .value
+            .to_f64()
+            .total_cmp(&other_converted.value.to_f64()))
+    }
+
+    /// Checks whether `other` (converted to the unit of `self`) is within an absolute
+    /// tolerance `abs` or a relative tolerance `rel` of `self`, i.e. whether
+    /// `|a - b| <= max(abs, rel · max(|a|, |b|))`. Quantities with incompatible
+    /// units are never approximately equal.
+    pub fn approx_eq_within(&self, other: &Quantity, rel: f64, abs: f64) -> bool {
+        if !self.unit.is_compatible_with(&other.unit) {
+            return false;
+        }
+        let Ok(other) = other.convert_to(&self.unit) else {
+            return false;
+        };
+
+        let a = self.value.to_f64();
+        let b = other.value.to_f64();
+        (a - b).abs() <= abs.max(rel * a.abs().max(b.abs()))
+    }
+
+    /// Checks whether this quantity is a whole multiple of `base` (like `120 s` of
+    /// `1 min`), i.e. whether `self / base` is within `tol` of an integer.
+    pub fn is_integer_multiple_of(&self, base: &Quantity, tol: f64) -> Result<bool> {
+        if base.is_zero() {
+            return Err(QuantityError::DivisionByZero);
+        }
+        let base = base.convert_to(&self.unit)?;
+
+        let ratio = self.value.to_f64() / base.value.to_f64();
+        Ok((ratio - ratio.round()).abs() <= tol)
+    }
+
+    /// Converts all quantities to the same target unit. In contrast to calling
+    /// [`Quantity::convert_to`] for each quantity, the base unit representation of
+    /// the target unit is only computed once. The results agree with individual
+    /// conversions (up to floating point rounding).
+    pub fn convert_all(quantities: &[Quantity], target_unit: &Unit) -> Vec<Result<Quantity>> {
+        if target_unit.affine_offset().is_some() {
+            return quantities
+                .iter()
+                .map(|q| q.convert_to(target_unit))
+                .collect();
+        }
+
+        let (target_base_unit_representation, factor) = target_unit.to_base_unit_representation();
+
+        quantities
+            .iter()
+            .map(|q| {
+                if &q.unit == target_unit
+                    || q.unit.affine_offset().is_some()
+                    || q.unsafe_value().to_f64().is_zero()
+                    || q.prefix_factors(target_unit).is_some()
+                {
+                    return q.convert_to(target_unit);
+                }
+
+                let base = q.to_base_unit_representation();
+                if base.unit == target_base_unit_representation {
+                    Ok(Quantity::new(base.value / factor, target_unit.clone()))
+                } else {
+                    Err(q.incompatible_units_error(target_unit))
+                }
+            })
+            .collect()
+    }
+
+    /// If both units consist of the same unit factors and only differ in their
+    /// prefixes (like `km` and `m`), return the combined prefix factors of both.
+    /// This avoids going through the base unit representation for such conversions.
+    fn prefix_factors(&self, target_unit: &Unit) -> Option<(Number, Number)> {
+        let own_factors: Vec<_> = self.unit.iter().collect();
+        let target_factors: Vec<_> = target_unit.iter().collect();
+        if own_factors.len() != target_factors.len()
+            || own_factors
+                .iter()
+                .zip(&target_factors)
+                .any(|(own, target)| {
+                    own.unit_id != target.unit_id || own.exponent != target.exponent
+                })
+        {
+            return None;
+        }
+
+        let prefix_factor = |factors: &[&UnitFactor]| -> Number {
+            factors
+                .iter()
+                .map(|f| f.prefix.factor().pow(&Number::from_rational(f.exponent)))
+                .product()
+        };
+        Some((prefix_factor(&own_factors), prefix_factor(&target_factors)))
+    }
+
+    fn convert_to_via_base_units(&self, target_unit: &Unit) -> Result<Quantity> {
+        // Remove common unit factors to reduce unnecessary conversion procedures
+        // For example: when converting from km/hour to mile/hour, there is no need
+        // to also perform the hour->second conversion, which would be needed, as
+        // we go back to base units for now. Removing common factors is just one
+        // heuristic, but it would be better to solve this in a more general way.
+        // For more details on this problem, see [1].
+        //
+        // [1] https://github.com/sharkdp/numbat/issues/118.
+        let (own_unit_reduced, target_unit_reduced) = self.unit.strip_common_with(target_unit);
+
+        let (own_base_unit_representation, own_factor) =
+            own_unit_reduced.to_base_unit_representation();
+        let (target_base_unit_representation, target_factor) =
+            target_unit_reduced.to_base_unit_representation();
+
+        if own_base_unit_representation == target_base_unit_representation {
+            Ok(Quantity::new(
+                self.value * own_factor / target_factor,
+                target_unit.clone(),
+            ))
+        } else {
+            Err(self.incompatible_units_error(target_unit))
+        }
+    }
+
+    /// The error for a failed conversion to `target_unit`. Note that the type checker
+    /// only allows conversions between units of the same physical dimension, so this
+    /// is usually caused by multiple base units for the same dimension.
+    fn incompatible_units_error(&self, target_unit: &Unit) -> QuantityError {
+        if self.unit.dimension() == target_unit.dimension() {
+            QuantityError::AmbiguousBaseUnits(self.unit.clone(), target_unit.clone())
+        } else {
+            QuantityError::IncompatibleUnits(self.unit.clone(), target_unit.clone())
+        }
+    }
+
+    /// Like [`Quantity::convert_to`], but also returns the conversion factor from the
+    /// unit of `self` to `target_unit` (e.g. `1000` for `km -> m`). For affine units
+    /// like `°C`, the factor only accounts for the scale, not for the offset.
+    pub fn convert_to_with_factor(&self, target_unit: &Unit) -> Result<(Quantity, Number)> {
+        let converted = self.convert_to(target_unit)?;
+        let factor = if self.unit.affine_offset().is_some() || target_unit.affine_offset().is_some()
+        {
+            self.unit.to_base_unit_representation().1 / target_unit.to_base_unit_representation().1
+        } else {
+            Quantity::from_unit(self.unit.clone())
+                .convert_to(target_unit)?
+                .value
+        };
+        Ok((converted, factor))
+    }
+
+    /// Rounds to the nearest whole multiple of `unit`.
+    pub fn round_to(&self, unit: &Unit) -> Result<Quantity> {
+        let converted = self.convert_to(unit)?;
+        Ok(Quantity::new_f64(
+            converted.value.to_f64().round(),
+            converted.unit,
+        ))
+    }
+
+    /// Rounds down to a whole multiple of `unit`.
+    pub fn floor_to(&self, unit: &Unit) -> Result<Quantity> {
+        let converted = self.convert_to(unit)?;
+        Ok(Quantity::new_f64(
+            converted.value.to_f64().floor(),
+            converted.unit,
+        ))
+    }
+
+    /// Rounds up to a whole multiple of `unit`.
+    pub fn ceil_to(&self, unit: &Unit) -> Result<Quantity> {
+        let converted = self.convert_to(unit)?;
+        Ok(Quantity::new_f64(
+            converted.value.to_f64().ceil(),
+            converted.unit,
+        ))
+    }
+
+    /// Converts this quantity to `unit` and returns the integer part of the value
+    /// (rounded towards zero) in that unit, e.g. `1 hour` for `75 min` in hours.
+    pub fn trunc_in(&self, unit: &Unit) -> Result<Quantity> {
+        let converted = self.convert_to(unit)?;
+        Ok(Quantity::new_f64(
+            converted.value.to_f64().trunc(),
+            converted.unit,
+        ))
+    }
+
+    /// Converts this quantity to `unit` and returns the fractional part of the value
+    /// in that unit, e.g. `0.25 hour` for `75 min` in hours.
+    pub fn fract_in(&self, unit: &Unit) -> Result<Quantity> {
+        let converted = self.convert_to(unit)?;
+        Ok(Quantity::new_f64(
+            converted.value.to_f64().fract(),
+            converted.unit,
+        ))
+    }
+
+    /// Decomposes this quantity into a sum of quantities in the given units, like
+    /// `5400 s` into `1 h + 30 min`. All but the last part are whole numbers, the
+    /// last part holds the remainder. The units are sorted by decreasing magnitude.
+    /// Unlike [`Quantity::to_mixed_radix`], every part of a negative quantity is
+    /// negative, such that the parts add up to the original quantity.
+    pub fn to_compound(&self, units: &[Unit]) -> Result<Vec<Quantity>> {
+        let Some(reference) = units.first() else {
+            return Ok(vec![]);
+        };
+
+        let mut units = units
+            .iter()
+            .map(|unit| {
+                let magnitude = Quantity::from_unit(unit.clone()).value_in(reference)?;
+                Ok((magnitude, unit.clone()))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        units.sort_by(|(a, _), (b, _)| b.total_cmp(a));
+        let units = units.into_iter().map(|(_, unit)| unit).collect::<Vec<_>>();
+
+        let negative = self.is_negative();
+        let (counts, remainder) = self.clone().abs().to_mixed_radix(&units)?;
+
+        let mut parts = counts
+            .into_iter()
+            .map(|(count, unit)| {
+                let count = if negative { -count } else { count };
+                Quantity::new_f64(count as f64, unit)
+            })
+            .collect::<Vec<_>>();
+        parts.push(if negative && !remainder.is_zero() {
+            -remainder
+        } else {
+            remainder
+        });
+
+        Ok(parts)
+    }
+
+    /// Decomposes this quantity into whole multiples of the given units (which need
+    /// to be sorted by decreasing magnitude) and a remainder in the last unit, like
+    /// `6.25 ft` into `6 ft` and `3 in`. For negative quantities, the sign is carried
+    /// by the first non-zero component (`-6.25 ft` becomes `-6 ft` and `3 in`).
+    ///
+    /// The whole multiples are returned as `(count, unit)` pairs, one for every unit
+    /// but the last, and the remainder is returned separately as a quantity in the
+    /// last unit, since it is generally not a whole number. See
+    /// [`Quantity::to_compound`] for a decomposition into a list of quantities.
+    pub fn to_mixed_radix(
+        &self,
+        units_descending: &[Unit],
+    ) -> Result<(Vec<(i64, Unit)>, Quantity)> {
+        let Some((smallest, larger)) = units_descending.split_last() else {
+            return Ok((vec![], self.clone()));
+        };
+
+        let total = self.value_in(smallest)?;
+        let mut remainder = total.abs();
+        let mut counts = Vec::with_capacity(larger.len());
+        for unit in larger {
+            let size = Quantity::from_unit(unit.clone()).value_in(smallest)?;
+            // Make sure that we get `7 ft` instead of `6 ft + 12 in` if the value in
+            // inches is slightly below 84 due to rounding errors.
+            let count = (remainder / size * (1.0 + 1e-12)).floor();
+            remainder = (remainder - count * size).max(0.0);
+            counts.push((count as i64, unit.clone()));
+        }
+
+        if total < 0.0 {
+            match counts.iter_mut().find(|(count, _)| *count != 0) {
+                Some((count, _)) => *count = -*count,
+                None => remainder = -remainder,
             }
         }
+
+        Ok((counts, Quantity::new_f64(remainder, smallest.clone())))
     }
 
     pub fn full_simplify(&self) -> Self {
@@ -130,26 +510,45 @@ impl Quantity {
             return scalar_result;
         }
 
-        // Heuristic 2
+        // Quantities with a single unit factor (like `5 km`) are already as simple as
+        // they get. Keep the unit as is, including its prefix.
         let unit = self.unit.canonicalized();
-        if unit.iter().count() > 1 {
-            for factor in unit.iter() {
-                let mut factor = factor.clone();
-                factor.exponent = Exponent::from_integer(1);
-                let factor_unit = Unit::from_factor(factor);
-
-                if let Some(alpha) = is_multiple_of(&unit, &factor_unit) {
-                    if alpha.is_integer() {
-                        let simplified_unit = factor_unit.power(alpha);
-                        if let Ok(q) = self.convert_to(&simplified_unit) {
-                            return q;
-                        }
+        if unit.iter().count() == 1 {
+            return Quantity::new(self.value, unit);
+        }
+
+        // Heuristic 2
+        for factor in unit.iter() {
+            let mut factor = factor.clone();
+            factor.exponent = Exponent::from_integer(1);
+            let factor_unit = Unit::from_factor(factor);
+
+            if let Some(alpha) = is_multiple_of(&unit, &factor_unit) {
+                if alpha.is_integer() {
+                    let simplified_unit = factor_unit.power(alpha);
+                    if let Ok(q) = self.convert_to(&simplified_unit) {
+                        return q;
                     }
                 }
             }
         }
 
         // Heuristic 3
+        self.merge_unit_groups(false)
+    }
+
+    /// Merges unit factors of the same dimension (like `m` and `cm`) into a single
+    /// factor. Unlike [`Quantity::full_simplify`], this only touches groups of such
+    /// factors, so units like `%` are not collapsed to scalars unless they cancel out
+    /// with another factor (e.g. `m/cm`).
+    pub fn merge_same_dimension(&self) -> Quantity {
+        self.merge_unit_groups(true)
+    }
+
+    /// Groups unit factors by dimension and converts each group to a single unit
+    /// factor. If `keep_single_factors` is set, groups with a single factor are
+    /// left unchanged.
+    fn merge_unit_groups(&self, keep_single_factors: bool) -> Quantity {
         let removed_exponent = |u: &UnitFactor| {
             let base_unit = u.unit_id.base_unit_and_factor().0;
             if let Some(first_factor) = base_unit.into_iter().next() {
@@ -169,6 +568,11 @@ impl Quantity {
             .group_by(|f| f.unit_id.sort_key())
         {
             let group_as_unit = Unit::from_factors(group.cloned());
+            if keep_single_factors && group_as_unit.iter().count() == 1 {
+                simplified_unit = simplified_unit * group_as_unit;
+                continue;
+            }
+
             let group_representative = group_as_unit
                 .iter()
                 .max_by(|&f1, &f2| {
@@ -209,105 +613,563 @@ impl Quantity {
         Quantity::new(self.value * factor, simplified_unit)
     }
 
+    /// Like [`Quantity::full_simplify`], but if the result is compatible with one of
+    /// the `preferred` units, it is expressed in that unit instead (e.g. `J` instead
+    /// of `kg·m²/s²`). Earlier entries in `preferred` take precedence.
+    pub fn simplify_preferring(&self, preferred: &[Unit]) -> Quantity {
+        let simplified = self.full_simplify();
+        preferred
+            .iter()
+            .filter(|unit| unit.is_compatible_with(&simplified.unit))
+            .find_map(|unit| simplified.convert_to(unit).ok())
+            .unwrap_or(simplified)
+    }
+
+    /// For quantities with a single prefixable unit factor (like `m` or `kg`), choose
+    /// the prefix that brings the value into the range `[1, 1000)` (or `[1, 1024)` for
+    /// binary prefixes). Only prefixes that the unit accepts are used, see
+    /// [`crate::unit::PrefixSupport`]. Metric prefixes are preferred, so `2048 B` becomes
+    /// `2.048 kB`. Binary prefixes are only chosen if the unit does not accept metric
+    /// prefixes, or if it already has a binary prefix (`2048 KiB` becomes `2 MiB`).
+    /// All other quantities (e.g. `5 m/s` or `7200 h`) are returned unchanged.
+    pub fn with_auto_prefix(&self) -> Quantity {
+        let unit = self.unit.canonicalized();
+        let factor = match unit.iter().collect::<Vec<_>>()[..] {
+            [factor] => factor.clone(),
+            _ => return self.clone(),
+        };
+
+        let prefix_support = factor.unit_id.prefix_support();
+        if factor.exponent != Exponent::from_integer(1)
+            || !(prefix_support.metric || prefix_support.binary)
+            || self.is_zero()
+            || !self.is_finite()
+        {
+            return self.clone();
+        }
+
+        let use_binary_prefixes =
+            prefix_support.binary && (factor.prefix.is_binary() || !prefix_support.metric);
+
+        let unprefixed_value = (self.value * factor.prefix.factor()).to_f64().abs();
+        let prefix = if use_binary_prefixes {
+            match (unprefixed_value.log2() / 10.0).floor() as i32 * 10 {
+                exponent if exponent <= 0 => Prefix::none(),
+                exponent => Prefix::Binary(exponent.min(80)),
+            }
+        } else {
+            let exponent = (unprefixed_value.log10() / 3.0).floor() as i32 * 3;
+            Prefix::from_exponent(exponent.clamp(-30, 30)).unwrap_or(factor.prefix)
+        };
+
+        self.convert_to(&Unit::from_factor(UnitFactor { prefix, ..factor }))
+            .unwrap_or_else(|_| self.clone())
+    }
+
+    /// If `self` can not be converted to `target_unit` because of differing physical
+    /// dimensions, returns both dimensions (see [`Unit::dimension`]), e.g. in order to
+    /// tell the user that they tried to convert a length to a time.
+    pub fn dimension_mismatch(&self, target_unit: &Unit) -> Option<(Dimension, Dimension)> {
+        let (own_dimension, target_dimension) = (self.unit.dimension(), target_unit.dimension());
+        (own_dimension != target_dimension).then_some((own_dimension, target_dimension))
+    }
+
+    /// Returns true if this quantity has no physical dimension, i.e. if its base unit
+    /// representation is a scalar (like for `m/m` or `%`). Following the SI, angles
+    /// like `rad` or `°` are also considered to be dimensionless.
+    pub fn is_dimensionless(&self) -> bool {
+        self.unit.to_base_unit_representation().0 == Unit::scalar()
+    }
+
+    /// Wraps an angle into the range `[0, full_turn)`. For example, `370°` wraps to
+    /// `10°` for a full turn of `360°`. The result is returned in the unit of `self`.
+    pub fn wrap_angle(&self, full_turn: &Quantity) -> Result<Quantity> {
+        let turn = self.full_turn_value(full_turn)?;
+        Ok(Quantity::new_f64(
+            self.value.to_f64().rem_euclid(turn),
+            self.unit.clone(),
+        ))
+    }
+
+    /// Like [`Quantity::wrap_angle`], but wraps into the range `[-half, half)` where
+    /// `half` is half of the full turn. For a full turn of `360°`, `270°` wraps to `-90°`.
+    pub fn wrap_symmetric(&self, full_turn: &Quantity) -> Result<Quantity> {
+        let turn = self.full_turn_value(full_turn)?;
+        let half = turn / 2.0;
+        Ok(Quantity::new_f64(
+            (self.value.to_f64() + half).rem_euclid(turn) - half,
+            self.unit.clone(),
+        ))
+    }
+
+    /// The value of `full_turn` in the unit of `self`. Both need to be angles.
+    fn full_turn_value(&self, full_turn: &Quantity) -> Result<f64> {
+        for q in [self, full_turn] {
+            if !q.is_dimensionless() {
+                return Err(q.incompatible_units_error(&Unit::radian()));
+            }
+        }
+        Ok(full_turn.convert_to(&self.unit)?.value.to_f64())
+    }
+
     pub fn as_scalar(&self) -> Result<Number> {
         Ok(self.convert_to(&Unit::scalar())?.value)
     }
 
+    /// Returns the numerical value of this quantity when expressed in `unit`.
+    pub fn value_in(&self, unit: &Unit) -> Result<f64> {
+        Ok(self.convert_to(unit)?.unsafe_value().to_f64())
+    }
+
     pub fn unsafe_value(&self) -> &Number {
         &self.value
     }
 
     pub fn power(self, exp: Quantity) -> Result<Self> {
         let exponent_as_scalar = exp.as_scalar()?.to_f64();
-        Ok(Quantity::new_f64(
-            self.value.to_f64().powf(exponent_as_scalar),
+        Ok(Quantity::new(
+            self.value.pow(&Number::from_f64(exponent_as_scalar)),
             self.unit.power(
                 Rational::from_f64(exponent_as_scalar).ok_or(QuantityError::NonRationalExponent)?,
             ),
         ))
     }
 
-    pub fn checked_div(self, other: Self) -> Option<Self> {
-        if other.is_zero() {
-            None
-        } else {
-            Some(self / other)
-        }
+    /// Raises this quantity to an integer power. In contrast to [`Quantity::power`],
+    /// this can not fail, and real values are raised with [`f64::powi`].
+    pub fn powi(self, n: i32) -> Self {
+        let value = match self.value {
+            Number::Real(x) => Number::from_f64(x.powi(n)),
+            value => value.pow(&Number::from_rational(Rational::from_integer(n.into()))),
+        };
+        Quantity::new(value, self.unit.powi(n.into()))
     }
-}
 
-impl From<&Number> for Quantity {
-    fn from(n: &Number) -> Self {
-        Quantity::from_scalar(n.to_f64())
+    /// Like [`Quantity::power`], but fails with [`QuantityError::NonRationalExponent`]
+    /// if the exponent is not exactly a rational number with a denominator of at
+    /// most 1 000 000 (like `π`), instead of raising the unit to an approximation.
+    pub fn try_power(&self, exp: &Quantity) -> Result<Self> {
+        self.try_power_with_max_denominator(exp, 1_000_000)
     }
-}
 
-impl std::ops::Add for &Quantity {
-    type Output = Result<Quantity>;
+    /// Like [`Quantity::try_power`], with a custom bound for the denominator of the
+    /// exponent.
+    pub fn try_power_with_max_denominator(
+        &self,
+        exp: &Quantity,
+        max_denominator: i128,
+    ) -> Result<Self> {
+        let exponent = exp.as_scalar()?.to_f64();
+        let rational_exponent = exact_rational_from_f64(exponent, max_denominator)
+            .ok_or(QuantityError::NonRationalExponent)?;
+        Ok(Quantity::new(
+            self.value.pow(&Number::from_f64(exponent)),
+            self.unit.clone().power(rational_exponent),
+        ))
+    }
 
-    fn add(self, rhs: Self) -> Self::Output {
-        if self.is_zero() {
-            Ok(rhs.clone())
-        } else if rhs.is_zero() {
-            Ok(self.clone())
-        } else {
-            Ok(Quantity {
-                value: self.value + rhs.convert_to(&self.unit)?.value,
-                unit: self.unit.clone(),
-            })
+    /// The square root. Negative (or complex) values yield a complex result.
+    pub fn sqrt(self) -> Result<Self> {
+        Ok(Quantity::new(
+            self.value.sqrt(),
+            self.unit.power(Rational::new(1, 2)),
+        ))
+    }
+
+    pub fn cbrt(self) -> Result<Self> {
+        Ok(Quantity::new(
+            self.value.cbrt(),
+            self.unit.power(Rational::new(1, 3)),
+        ))
+    }
+
+    pub fn abs(self) -> Self {
+        Quantity::new(self.value.abs(), self.unit)
+    }
+
+    /// Like [`Quantity::convert_to`], but zero quantities are only converted to units
+    /// of the same dimension. `convert_to` accepts zero quantities for any target unit,
+    /// which is not what we want when combining two quantities (`min(1 km, 0 s)`). A
+    /// dimensionless zero still acts as a polymorphic zero, see `total_cmp`.
+    fn convert_to_compatible(&self, target_unit: &Unit) -> Result<Quantity> {
+        if self.is_zero() && !self.unit.is_scalar() && !self.unit.is_compatible_with(target_unit) {
+            return Err(self.incompatible_units_error(target_unit));
         }
+        self.convert_to(target_unit)
     }
-}
 
-impl std::ops::Sub for &Quantity {
-    type Output = Result<Quantity>;
+    /// Returns the smaller of the two quantities, expressed in the unit of `self`.
+    pub fn min(self, other: Self) -> Result<Self> {
+        let other = other.convert_to_compatible(&self.unit)?;
+        Ok(if other.value < self.value {
+            other
+        } else {
+            self
+        })
+    }
 
-    fn sub(self, rhs: Self) -> Self::Output {
-        if self.is_zero() {
-            Ok(-rhs.clone())
-        } else if rhs.is_zero() {
-            Ok(self.clone())
+    /// Returns the larger of the two quantities, expressed in the unit of `self`.
+    pub fn max(self, other: Self) -> Result<Self> {
+        let other = other.convert_to_compatible(&self.unit)?;
+        Ok(if other.value > self.value {
+            other
         } else {
-            Ok(Quantity {
-                value: self.value - rhs.convert_to(&self.unit)?.value,
-                unit: self.unit.clone(),
-            })
-        }
+            self
+        })
     }
-}
 
-impl std::ops::Mul for Quantity {
-    type Output = Quantity;
+    /// Restricts the quantity to the interval `[lo, hi]`, expressed in the unit of `self`.
+    pub fn clamp(self, lo: Self, hi: Self) -> Result<Self> {
+        let lo = lo.convert_to_compatible(&self.unit)?;
+        let hi = hi.convert_to_compatible(&self.unit)?;
+        debug_assert!(lo.value <= hi.value, "lower bound exceeds upper bound");
 
-    fn mul(self, rhs: Self) -> Self::Output {
-        Quantity {
-            value: self.value * rhs.value,
-            unit: self.unit * rhs.unit,
-        }
+        self.max(lo)?.min(hi)
     }
-}
 
-impl std::ops::Div for Quantity {
-    type Output = Quantity;
+    /// Computes `sqrt(self² + other²)` without intermediate overflow, e.g. for the
+    /// magnitude of two perpendicular components. The result is expressed in the
+    /// unit of `self`.
+    pub fn hypot(&self, other: &Quantity) -> Result<Quantity> {
+        let other = other.convert_to_compatible(&self.unit)?;
+        Ok(Quantity::new_f64(
+            self.value.to_f64().hypot(other.value.to_f64()),
+            self.unit.clone(),
+        ))
+    }
 
-    fn div(self, rhs: Self) -> Self::Output {
-        Quantity {
-            value: self.value / rhs.value,
-            unit: self.unit / rhs.unit,
-        }
+    /// Applies `f` to the value and keeps the unit, e.g. to clamp a value.
+    pub fn map_value(&self, f: impl FnOnce(f64) -> f64) -> Quantity {
+        Quantity::new_f64(f(self.value.to_f64()), self.unit.clone())
     }
-}
 
-impl std::ops::Neg for Quantity {
-    type Output = Quantity;
+    /// Applies `f` to the value of a dimensionless quantity and returns a scalar.
+    /// Dimensionless units like `%` or angles like `°` are converted to plain scalars
+    /// (radians, for angles) first.
+    pub fn map_value_dimensionless(&self, f: impl FnOnce(f64) -> f64) -> Result<Quantity> {
+        Ok(Quantity::from_scalar(f(self.as_scalar()?.to_f64())))
+    }
 
-    fn neg(self) -> Self::Output {
-        Quantity {
-            value: -self.value,
-            unit: self.unit,
-        }
+    /// The sine of an angle (or a dimensionless value, interpreted in radians).
+    pub fn sin(&self) -> Result<Quantity> {
+        self.map_value_dimensionless(f64::sin)
     }
-}
+
+    /// The cosine of an angle (or a dimensionless value, interpreted in radians).
+    pub fn cos(&self) -> Result<Quantity> {
+        self.map_value_dimensionless(f64::cos)
+    }
+
+    /// The tangent of an angle (or a dimensionless value, interpreted in radians).
+    pub fn tan(&self) -> Result<Quantity> {
+        self.map_value_dimensionless(f64::tan)
+    }
+
+    /// The exponential function of a dimensionless value.
+    pub fn exp(&self) -> Result<Quantity> {
+        self.map_value_dimensionless(f64::exp)
+    }
+
+    /// The natural logarithm of a dimensionless value.
+    pub fn ln(&self) -> Result<Quantity> {
+        self.map_value_dimensionless(f64::ln)
+    }
+
+    /// The decimal logarithm of a dimensionless value.
+    pub fn log10(&self) -> Result<Quantity> {
+        self.map_value_dimensionless(f64::log10)
+    }
+
+    /// The binary logarithm of a dimensionless value.
+    pub fn log2(&self) -> Result<Quantity> {
+        self.map_value_dimensionless(f64::log2)
+    }
+
+    /// Expresses the ratio `self / reference` in decibels, i.e. `10·log10(ratio)` for
+    /// power quantities (`power_ratio = true`) and `20·log10(ratio)` for field (root
+    /// power) quantities.
+    pub fn to_decibels(&self, reference: &Quantity, power_ratio: bool) -> Result<Number> {
+        let reference = reference.convert_to(&self.unit)?;
+        let ratio = (self.value / reference.value).to_f64();
+        Ok(Number::from_f64(
+            decibel_factor(power_ratio) * ratio.log10(),
+        ))
+    }
+
+    /// The inverse of [`Quantity::to_decibels`]: the quantity that is `decibels` above
+    /// `reference`, expressed in the unit of `reference`.
+    pub fn from_decibels(decibels: Number, reference: &Quantity, power_ratio: bool) -> Quantity {
+        let ratio = 10f64.powf(decibels.to_f64() / decibel_factor(power_ratio));
+        reference.clone().mul_scalar(ratio)
+    }
+
+    /// Applies `f` to the value of a dimensionless quantity and returns the result
+    /// as an angle, in radians.
+    fn map_scalar_to_angle(&self, f: impl Fn(f64) -> f64) -> Result<Quantity> {
+        Ok(Quantity::new_f64(
+            f(self.as_scalar()?.to_f64()),
+            Unit::radian(),
+        ))
+    }
+
+    /// The inverse sine of a dimensionless value, in radians.
+    pub fn asin(&self) -> Result<Quantity> {
+        self.map_scalar_to_angle(f64::asin)
+    }
+
+    /// The inverse cosine of a dimensionless value, in radians.
+    pub fn acos(&self) -> Result<Quantity> {
+        self.map_scalar_to_angle(f64::acos)
+    }
+
+    /// The inverse tangent of a dimensionless value, in radians.
+    pub fn atan(&self) -> Result<Quantity> {
+        self.map_scalar_to_angle(f64::atan)
+    }
+
+    /// The angle (in radians) of the point `(x, y)`, where `self` is `y`. Both
+    /// coordinates need to be of compatible units.
+    pub fn atan2(&self, x: &Quantity) -> Result<Quantity> {
+        let x = x.convert_to(&self.unit)?;
+        Ok(Quantity::new_f64(
+            self.value.to_f64().atan2(x.value.to_f64()),
+            Unit::radian(),
+        ))
+    }
+
+    /// Linearly interpolates between `self` (at `t = 0`) and `other` (at `t = 1`).
+    /// The result is expressed in the unit of `self`. Values of `t` outside of
+    /// `[0, 1]` extrapolate.
+    pub fn lerp(&self, other: &Quantity, t: f64) -> Result<Quantity> {
+        let other = other.convert_to_compatible(&self.unit)?;
+        Ok(Quantity::new(
+            self.value * Number::from_f64(1.0 - t) + other.value * Number::from_f64(t),
+            self.unit.clone(),
+        ))
+    }
+
+    /// Adds `rhs` in place. The unit of `self` is kept, unless an affine unit (like
+    /// `°C`) is involved: just like for `+`, both operands are converted to base units
+    /// first in this case. On error, `self` is left untouched.
+    pub fn add_assign(&mut self, rhs: &Quantity) -> Result<()> {
+        if self.involves_affine_unit(rhs) {
+            *self = (&*self + rhs)?;
+        } else {
+            self.value = self.value + rhs.convert_to(&self.unit)?.value;
+        }
+        Ok(())
+    }
+
+    /// Subtracts `rhs` in place. The unit of `self` is kept, unless an affine unit
+    /// is involved (see [`Quantity::add_assign`]). On error, `self` is left untouched.
+    pub fn sub_assign(&mut self, rhs: &Quantity) -> Result<()> {
+        if self.involves_affine_unit(rhs) {
+            *self = (&*self - rhs)?;
+        } else {
+            self.value = self.value - rhs.convert_to(&self.unit)?.value;
+        }
+        Ok(())
+    }
+
+    /// Multiplies the value by a dimensionless factor, leaving the unit untouched.
+    pub fn mul_scalar(self, s: f64) -> Self {
+        Quantity::new(self.value * Number::from_f64(s), self.unit)
+    }
+
+    /// Divides the value by a dimensionless factor, leaving the unit untouched.
+    pub fn div_scalar(self, s: f64) -> Self {
+        Quantity::new(self.value / Number::from_f64(s), self.unit)
+    }
+
+    pub fn reciprocal(self) -> Self {
+        Quantity::new(
+            Number::from_f64(1.0) / self.value,
+            Unit::scalar() / self.unit,
+        )
+    }
+
+    /// Like `+`, but returns [`QuantityError::Overflow`] if the result is not finite
+    /// although both operands are.
+    pub fn checked_add(&self, other: &Self) -> Result<Self> {
+        Self::check_overflow(&(self + other)?, self, other)
+    }
+
+    /// Like `-`, but returns [`QuantityError::Overflow`] if the result is not finite
+    /// although both operands are.
+    pub fn checked_sub(&self, other: &Self) -> Result<Self> {
+        Self::check_overflow(&(self - other)?, self, other)
+    }
+
+    /// Like `*`, but returns [`QuantityError::Overflow`] if the result is not finite
+    /// although both operands are.
+    pub fn checked_mul(&self, other: &Self) -> Result<Self> {
+        Self::check_overflow(&(self.clone() * other.clone()), self, other)
+    }
+
+    /// Like `/`, but returns [`QuantityError::DivisionByZero`] if `other` is zero and
+    /// [`QuantityError::Overflow`] if the result is not finite although both operands are.
+    /// This is the counterpart of [`Quantity::checked_add`] and friends, which can not be
+    /// called `checked_div`, since [`Quantity::checked_div`] only checks for zero.
+    pub fn try_div(&self, other: &Self) -> Result<Self> {
+        if other.is_zero() {
+            return Err(QuantityError::DivisionByZero);
+        }
+        Self::check_overflow(&(self.clone() / other.clone()), self, other)
+    }
+
+    pub fn checked_div(self, other: Self) -> Option<Self> {
+        if other.is_zero() {
+            None
+        } else {
+            Some(self / other)
+        }
+    }
+
+    fn check_overflow(result: &Quantity, lhs: &Quantity, rhs: &Quantity) -> Result<Quantity> {
+        if !result.is_finite() && lhs.is_finite() && rhs.is_finite() {
+            Err(QuantityError::Overflow)
+        } else {
+            Ok(result.clone())
+        }
+    }
+}
+
+fn decibel_factor(power_ratio: bool) -> f64 {
+    if power_ratio {
+        10.0
+    } else {
+        20.0
+    }
+}
+
+impl From<&Number> for Quantity {
+    fn from(n: &Number) -> Self {
+        Quantity::from_scalar(n.to_f64())
+    }
+}
+
+/// Returns the scalar value of a dimensionless quantity (like `3` or `50 m/m`).
+impl TryFrom<Quantity> for f64 {
+    type Error = QuantityError;
+
+    fn try_from(q: Quantity) -> Result<Self> {
+        if !q.is_dimensionless() {
+            return Err(QuantityError::IncompatibleUnits(q.unit, Unit::scalar()));
+        }
+        Ok(q.as_scalar()?.to_f64())
+    }
+}
+
+// Arithmetic on quantities with affine units (like `°C`) is always performed on
+// their absolute values in base units. For example, `20 °C - 10 °C` is `10 K`.
+
+impl std::ops::Add for &Quantity {
+    type Output = Result<Quantity>;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        if self.involves_affine_unit(rhs) {
+            &self.clone().without_affine_unit() + &rhs.clone().without_affine_unit()
+        } else if self.is_zero() {
+            Ok(rhs.clone())
+        } else if rhs.is_zero() {
+            Ok(self.clone())
+        } else {
+            Ok(Quantity {
+                value: self.value + rhs.convert_to(&self.unit)?.value,
+                unit: self.unit.clone(),
+            })
+        }
+    }
+}
+
+impl std::ops::Sub for &Quantity {
+    type Output = Result<Quantity>;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        if self.involves_affine_unit(rhs) {
+            &self.clone().without_affine_unit() - &rhs.clone().without_affine_unit()
+        } else if self.is_zero() {
+            Ok(-rhs.clone())
+        } else if rhs.is_zero() {
+            Ok(self.clone())
+        } else {
+            Ok(Quantity {
+                value: self.value - rhs.convert_to(&self.unit)?.value,
+                unit: self.unit.clone(),
+            })
+        }
+    }
+}
+
+impl std::ops::Rem for &Quantity {
+    type Output = Result<Quantity>;
+
+    fn rem(self, rhs: Self) -> Self::Output {
+        Ok(Quantity {
+            value: self.value % rhs.convert_to_compatible(&self.unit)?.value,
+            unit: self.unit.clone(),
+        })
+    }
+}
+
+impl std::ops::Mul for Quantity {
+    type Output = Quantity;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        let (lhs, rhs) = Quantity::without_affine_units(self, rhs);
+        Quantity {
+            value: lhs.value * rhs.value,
+            unit: lhs.unit * rhs.unit,
+        }
+    }
+}
+
+impl std::ops::Div for Quantity {
+    type Output = Quantity;
+
+    fn div(self, rhs: Self) -> Self::Output {
+        let (lhs, rhs) = Quantity::without_affine_units(self, rhs);
+        Quantity {
+            value: lhs.value / rhs.value,
+            unit: lhs.unit / rhs.unit,
+        }
+    }
+}
+
+/// Sums up quantities in the unit of the first element, see [`Quantity::add_assign`].
+/// Fails if the units of the other elements are incompatible. The sum of an empty
+/// iterator is a dimensionless zero.
+impl std::iter::Sum<Quantity> for Result<Quantity> {
+    fn sum<I: Iterator<Item = Quantity>>(mut iter: I) -> Self {
+        let Some(mut total) = iter.next() else {
+            return Ok(Quantity::from_scalar(0.0));
+        };
+
+        for q in iter {
+            total.add_assign(&q)?;
+        }
+        Ok(total)
+    }
+}
+
+/// Multiplies quantities, including their units. The product of an empty iterator
+/// is a dimensionless one.
+impl std::iter::Product<Quantity> for Quantity {
+    fn product<I: Iterator<Item = Quantity>>(iter: I) -> Self {
+        iter.fold(Quantity::from_scalar(1.0), |acc, q| acc * q)
+    }
+}
+
+impl std::ops::Neg for Quantity {
+    type Output = Quantity;
+
+    fn neg(self) -> Self::Output {
+        Quantity {
+            value: -self.value,
+            unit: self.unit,
+        }
+    }
+}
 
 impl PartialEq for Quantity {
     fn eq(&self, other: &Self) -> bool {
@@ -317,132 +1179,2229 @@ impl PartialEq for Quantity {
             false
         }
     }
-}
+}
+
+impl Eq for Quantity {}
+
+impl std::hash::Hash for Quantity {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        // Equality converts between units, so quantities that compare equal can have
+        // values that differ by rounding errors, even in base units. Only the base unit
+        // is hashed for this reason. Zero quantities compare equal regardless of their
+        // unit, so nothing is hashed for them (`-273.15 °C` is zero in base units).
+        let base_unit_representation = self.to_base_unit_representation();
+        if !base_unit_representation.is_zero() {
+            base_unit_representation.unit.hash(state);
+        }
+    }
+}
+
+impl PartialOrd for Quantity {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        // `convert_to` accepts zero quantities for any target unit. A dimensionless
+        // zero acts as a polymorphic zero here (as in `x > 0`), but a zero length
+        // and a zero time are not orderable.
+        let is_polymorphic_zero = |q: &Quantity| q.is_zero() && q.unit.is_scalar();
+        if (self.is_zero() || other.is_zero())
+            && !is_polymorphic_zero(self)
+            && !is_polymorphic_zero(other)
+            && !self.unit.is_compatible_with(&other.unit)
+        {
+            return None;
+        }
+
+        let other_converted = other.convert_to(self.unit()).ok()?;
+        self.value.partial_cmp(&other_converted.value)
+    }
+}
+
+#[cfg(feature = "approx")]
+impl approx::AbsDiffEq for Quantity {
+    type Epsilon = f64;
+
+    fn default_epsilon() -> Self::Epsilon {
+        f64::default_epsilon()
+    }
+
+    fn abs_diff_eq(&self, other: &Self, epsilon: Self::Epsilon) -> bool {
+        if !self.unit.is_compatible_with(&other.unit) {
+            return false;
+        }
+        match other.convert_to(&self.unit) {
+            Ok(other) => self
+                .value
+                .to_f64()
+                .abs_diff_eq(&other.value.to_f64(), epsilon),
+            Err(_) => false,
+        }
+    }
+}
+
+#[cfg(feature = "approx")]
+impl approx::RelativeEq for Quantity {
+    fn default_max_relative() -> Self::Epsilon {
+        f64::default_max_relative()
+    }
+
+    fn relative_eq(
+        &self,
+        other: &Self,
+        epsilon: Self::Epsilon,
+        max_relative: Self::Epsilon,
+    ) -> bool {
+        if !self.unit.is_compatible_with(&other.unit) {
+            return false;
+        }
+        match other.convert_to(&self.unit) {
+            Ok(other) => {
+                self.value
+                    .to_f64()
+                    .relative_eq(&other.value.to_f64(), epsilon, max_relative)
+            }
+            Err(_) => false,
+        }
+    }
+}
+
+impl Quantity {
+    fn pretty_print_with_formatted_value(&self, formatted_number: String) -> Markup {
+        let unit_str = format!("{}", self.unit());
+
+        m::value(formatted_number)
+            + if matches!(unit_str.as_str(), "°" | "%" | "‰" | "") {
+                m::empty()
+            } else {
+                m::space()
+            }
+            + m::unit(unit_str)
+    }
+
+    /// Like [`PrettyPrint::pretty_print`], but in engineering notation: the value is
+    /// written as a mantissa in `[1, 1000)` times a power of ten whose exponent is a
+    /// multiple of three, e.g. `7.3×10³ m²`. The power of ten is omitted if the
+    /// exponent is zero.
+    pub fn pretty_print_engineering(&self) -> Markup {
+        let value = self.value.to_f64();
+
+        let (mantissa, exponent) = if value == 0.0 || !value.is_finite() {
+            (value, 0)
+        } else {
+            // Shift the decimal point in the shortest decimal representation of the
+            // value instead of dividing by a power of ten, to avoid round-off errors.
+            let scientific = format!("{value:e}");
+            let (digits, exponent) = scientific
+                .split_once('e')
+                .expect("scientific notation contains an exponent");
+            let exponent: i32 = exponent.parse().expect("exponent is an integer");
+            let engineering_exponent = exponent.div_euclid(3) * 3;
+            let mantissa = format!("{digits}e{}", exponent - engineering_exponent)
+                .parse()
+                .expect("mantissa is a valid number");
+            (mantissa, engineering_exponent)
+        };
+
+        let mantissa = Number::from_f64(mantissa).pretty_print();
+        let formatted_number = if exponent == 0 {
+            mantissa
+        } else {
+            format!("{mantissa}×10{}", superscript_integer(exponent))
+        };
+
+        self.pretty_print_with_formatted_value(formatted_number)
+    }
+
+    /// Like [`PrettyPrint::pretty_print`], but in scientific notation with the given
+    /// number of digits after the decimal point of the mantissa, e.g. `7.3×10² m²` for
+    /// one digit. The power of ten is always written out, also for values in `[1, 10)`
+    /// (`2.0×10⁰ m`). Non-finite values are printed as usual.
+    pub fn pretty_print_scientific(&self, mantissa_digits: usize) -> Markup {
+        let value = self.value.to_f64();
+        if !value.is_finite() {
+            return self.pretty_print();
+        }
+
+        let scientific = format!("{value:.mantissa_digits$e}");
+        let (mantissa, exponent) = scientific
+            .split_once('e')
+            .expect("scientific notation contains an exponent");
+        let exponent: i32 = exponent.parse().expect("exponent is an integer");
+
+        self.pretty_print_with_formatted_value(format!(
+            "{mantissa}×10{}",
+            superscript_integer(exponent)
+        ))
+    }
+
+    /// Like [`PrettyPrint::pretty_print`], but the value is rounded to the given number
+    /// of significant figures. Trailing zeros within that precision are kept, e.g. `1.50 m`
+    /// for three significant figures.
+    pub fn pretty_print_with_precision(&self, sig_figs: usize) -> Markup {
+        let value = self.value.to_f64();
+        if !value.is_finite() {
+            return self.pretty_print();
+        }
+
+        let precision = sig_figs.max(1) - 1;
+
+        // Let the standard library perform the rounding, in order to learn the
+        // decimal exponent of the *rounded* value (9.996 → 1.00e1).
+        let scientific = format!("{value:.precision$e}");
+        let (_, exponent) = scientific
+            .split_once('e')
+            .expect("scientific notation contains an exponent");
+        let exponent: i64 = exponent.parse().expect("exponent is an integer");
+
+        let decimals = precision as i64 - exponent;
+        let formatted_number = if decimals >= 0 {
+            format!("{value:.0$}", decimals as usize)
+        } else {
+            let rounded: f64 = scientific.parse().expect("valid number");
+            format!("{rounded:.0}")
+        };
+
+        self.pretty_print_with_formatted_value(formatted_number)
+    }
+}
+
+impl Quantity {
+    /// Parses a quantity like `9.81 m/s^2`, `90°` or `42`. The space between the
+    /// value and the unit is optional. See [`Unit::parse`] for the unit syntax.
+    pub fn parse(
+        input: &str,
+        unit_lookup: impl Fn(&str) -> Option<Unit>,
+    ) -> std::result::Result<Quantity, QuantityParseError> {
+        let input = input.trim();
+        let (value, unit) = input.split_at(Self::number_literal_length(input));
+
+        let value = value.parse::<Number>()?;
+        let unit = match unit.trim() {
+            "" => Unit::scalar(),
+            unit => Unit::parse(unit, unit_lookup)?,
+        };
+        Ok(Quantity::new(value, unit))
+    }
+
+    /// The length of the number literal at the start of `input`, like `-6.022e23`.
+    fn number_literal_length(input: &str) -> usize {
+        let bytes = input.as_bytes();
+        let is_digit_at = |i: usize| bytes.get(i).is_some_and(|b| b.is_ascii_digit());
+
+        let mut end = usize::from(matches!(bytes.first(), Some(b'+' | b'-')));
+        while bytes
+            .get(end)
+            .is_some_and(|b| b.is_ascii_digit() || matches!(b, b'.' | b'_'))
+        {
+            end += 1;
+        }
+
+        // Only treat an `e` as exponent if it is followed by digits, as in `1e3`,
+        // but not in `1 eV`.
+        if matches!(bytes.get(end), Some(b'e' | b'E')) {
+            let mut exponent_start = end + 1;
+            if matches!(bytes.get(exponent_start), Some(b'+' | b'-')) {
+                exponent_start += 1;
+            }
+            if is_digit_at(exponent_start) {
+                end = exponent_start;
+                while is_digit_at(end) || bytes.get(end) == Some(&b'_') {
+                    end += 1;
+                }
+            }
+        }
+
+        end
+    }
+}
+
+impl PrettyPrint for Quantity {
+    fn pretty_print(&self) -> Markup {
+        let value = self.unsafe_value().pretty_print();
+        // Complex values like `1 + 2i` need parentheses in front of a unit
+        if self.value.is_complex() && value.contains(' ') && !self.unit.is_scalar() {
+            self.pretty_print_with_formatted_value(format!("({value})"))
+        } else {
+            self.pretty_print_with_formatted_value(value)
+        }
+    }
+}
+
+impl Quantity {
+    /// Renders this quantity as (math mode) LaTeX, e.g. `1\,\mathrm{N}\cdot\mathrm{m}`.
+    pub fn to_latex(&self) -> String {
+        use crate::markup::{Formatter, LatexFormatter};
+
+        LatexFormatter {}.format(&self.pretty_print(), false)
+    }
+
+    /// Renders this quantity as Markdown inline math, e.g. `$1\,\mathrm{N}\cdot\mathrm{m}$`.
+    pub fn to_markdown(&self) -> String {
+        use crate::markup::{Formatter, MarkdownFormatter};
+
+        MarkdownFormatter {}.format(&self.pretty_print(), false)
+    }
+
+    /// Renders this quantity using ASCII characters only, e.g. `9.81 m/s^2`.
+    pub fn to_ascii(&self) -> String {
+        use crate::markup::{AsciiFormatter, Formatter};
+
+        AsciiFormatter {}.format(&self.pretty_print(), false)
+    }
+
+    /// Renders this quantity as HTML, e.g. `<span class="numbat-value">9.81</span>
+    /// <span class="numbat-unit">m/s<sup>2</sup></span>`.
+    #[cfg(feature = "html-formatter")]
+    pub fn to_html(&self) -> String {
+        use crate::html_formatter::HtmlFormatter;
+        use crate::markup::Formatter;
+
+        HtmlFormatter {}.format(&self.pretty_print(), false)
+    }
+}
+
+impl std::fmt::Display for Quantity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        use crate::markup::{Formatter, PlainTextFormatter};
+
+        let markup = self.pretty_print();
+        let formatter = PlainTextFormatter {};
+        write!(f, "{}", formatter.format(&markup, false).trim())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        prefix::Prefix,
+        prefix_parser::AcceptsPrefix,
+        unit::{CanonicalName, PrefixSupport},
+    };
+
+    use super::*;
+
+    #[test]
+    fn conversion_trivial() {
+        let meter = Unit::meter();
+        let second = Unit::second();
+
+        let length = Quantity::new_f64(2.0, meter.clone());
+
+        assert!(length.convert_to(&meter).is_ok());
+
+        assert!(length.convert_to(&second).is_err());
+        assert!(length.convert_to(&Unit::scalar()).is_err());
+    }
+
+    #[test]
+    fn with_unit() {
+        let length = Quantity::new_f64(2.0, Unit::meter());
+
+        let time = length.clone().with_unit(Unit::second());
+        assert_eq!(time, Quantity::new_f64(2.0, Unit::second()));
+        assert!(length.convert_to(&Unit::second()).is_err());
+
+        let reinterpreted = length.clone().with_unit(Unit::centimeter());
+        assert_eq!(reinterpreted.unsafe_value().to_f64(), 2.0);
+        assert_eq!(
+            length.convert_to(&Unit::centimeter()).unwrap(),
+            Quantity::new_f64(200.0, Unit::centimeter())
+        );
+    }
+
+    #[test]
+    fn conversion_basic() {
+        use approx::assert_relative_eq;
+
+        let meter = Unit::meter();
+        let foot = Unit::new_derived(
+            "foot",
+            CanonicalName::new("ft", AcceptsPrefix::none()),
+            Number::from_f64(0.3048),
+            meter.clone(),
+        );
+
+        let length = Quantity::new_f64(2.0, meter.clone());
+
+        let length_in_foot = length.convert_to(&foot).expect("conversion succeeds");
+        assert_eq!(length_in_foot.unsafe_value().to_f64(), 2.0 / 0.3048);
+
+        let length_converted_back_to_meter = length_in_foot
+            .convert_to(&meter)
+            .expect("conversion succeeds");
+        assert_relative_eq!(
+            length_converted_back_to_meter.unsafe_value().to_f64(),
+            2.0,
+            epsilon = 1e-6
+        );
+    }
+
+    #[test]
+    fn prefixes() {
+        use crate::prefix::Prefix;
+
+        use approx::assert_relative_eq;
+
+        let meter = Unit::meter();
+        let centimeter = Unit::meter().with_prefix(Prefix::centi());
+
+        let length = Quantity::new_f64(2.5, meter.clone());
+        {
+            let length_in_centimeter = length.convert_to(&centimeter).expect("conversion succeeds");
+            assert_relative_eq!(
+                length_in_centimeter.unsafe_value().to_f64(),
+                250.0,
+                epsilon = 1e-6
+            );
+
+            let length_converted_back_to_meter = length_in_centimeter
+                .convert_to(&meter)
+                .expect("conversion succeeds");
+            assert_relative_eq!(
+                length_converted_back_to_meter.unsafe_value().to_f64(),
+                2.5,
+                epsilon = 1e-6
+            );
+        }
+        {
+            let volume = length
+                .power(Quantity::from_scalar(3.0))
+                .expect("exponent is scalar");
+
+            let volume_in_centimeter3 = volume
+                .convert_to(&centimeter.powi(3))
+                .expect("conversion succeeds");
+            assert_relative_eq!(
+                volume_in_centimeter3.unsafe_value().to_f64(),
+                15_625_000.0,
+                epsilon = 1e-6
+            );
+        }
+    }
+
+    #[test]
+    fn measured() {
+        let a = Quantity::new(Number::from_measurement(10.0, 1.0), Unit::meter());
+        let b = Quantity::new(Number::from_measurement(5.0, 1.0), Unit::meter());
+        assert_eq!(
+            (&a + &b).unwrap(),
+            Quantity::new(Number::from_measurement(15.0, 2.0f64.sqrt()), Unit::meter())
+        );
+
+        let length = Quantity::new(Number::from_measurement(1.5, 0.02), Unit::kilometer());
+        assert_eq!(length.to_string(), "1.50 ± 0.02 km");
+        let length = length.convert_to(&Unit::meter()).unwrap();
+        assert_eq!(
+            *length.unsafe_value(),
+            Number::from_measurement(1500.0, 20.0)
+        );
+        assert_eq!(length.to_string(), "1500 ± 20 m");
+    }
+
+    #[test]
+    fn interval() {
+        let a = Quantity::new(Number::from_interval(1.0, 2.0), Unit::meter());
+        let b = Quantity::new(Number::from_interval(3.0, 4.0), Unit::meter());
+        assert_eq!(
+            (&a + &b).unwrap(),
+            Quantity::new(Number::from_interval(4.0, 6.0), Unit::meter())
+        );
+
+        let length = Quantity::new(Number::from_interval(1.0, 2.0), Unit::kilometer());
+        assert_eq!(
+            length.convert_to(&Unit::meter()).unwrap(),
+            Quantity::new(Number::from_interval(1000.0, 2000.0), Unit::meter())
+        );
+        assert_eq!(length.to_string(), "[1, 2] km");
+    }
+
+    #[test]
+    fn parse() {
+        let lookup = |name: &str| match name {
+            "m" => Some(Unit::meter()),
+            "s" => Some(Unit::second()),
+            "°" => Some(Unit::degree()),
+            _ => None,
+        };
+
+        let q = Quantity::parse("9.81 m/s^2", lookup).unwrap();
+        assert_eq!(
+            q,
+            Quantity::new_f64(9.81, Unit::meter() / Unit::second().powi(2))
+        );
+        assert_eq!(q.unit(), &(Unit::meter() / Unit::second().powi(2)));
+
+        let angle = Quantity::parse("90°", lookup).unwrap();
+        assert_eq!(angle.unit(), &Unit::degree());
+        assert_eq!(angle.unsafe_value().to_f64(), 90.0);
+        assert_eq!(angle.to_string(), "90°");
+        assert_eq!(Quantity::parse("90 °", lookup), Ok(angle));
+
+        let scalar = Quantity::parse("42", lookup).unwrap();
+        assert_eq!(scalar.unit(), &Unit::scalar());
+        assert_eq!(scalar.unsafe_value().to_f64(), 42.0);
+
+        assert_eq!(
+            Quantity::parse("-1.5e3m", lookup)
+                .unwrap()
+                .unsafe_value()
+                .to_f64(),
+            -1500.0
+        );
+
+        assert_eq!(
+            Quantity::parse("5 bananas", lookup),
+            Err(QuantityParseError::Unit(UnitParseError::UnknownUnit(
+                "bananas".into()
+            )))
+        );
+        assert_eq!(
+            Quantity::parse("m", lookup),
+            Err(QuantityParseError::Number(ParseNumberError::Empty))
+        );
+    }
+
+    #[test]
+    fn convert_to_with_factor() {
+        use approx::assert_relative_eq;
+
+        let (converted, factor) = Quantity::new_f64(1.0, Unit::kilometer())
+            .convert_to_with_factor(&Unit::meter())
+            .unwrap();
+        assert_eq!(converted, Quantity::new_f64(1000.0, Unit::meter()));
+        assert_eq!(factor, Number::from_f64(1000.0));
+
+        let (converted, factor) = Quantity::new_f64(1.0, Unit::meter())
+            .convert_to_with_factor(&Unit::meter())
+            .unwrap();
+        assert_eq!(converted, Quantity::new_f64(1.0, Unit::meter()));
+        assert_eq!(factor, Number::from_f64(1.0));
+
+        let q = Quantity::new_f64(2.5, Unit::hour());
+        let (converted, factor) = q.convert_to_with_factor(&Unit::second()).unwrap();
+        assert_eq!(*converted.unsafe_value(), *q.unsafe_value() * factor);
+
+        let (converted, factor) = Quantity::new_f64(10.0, Unit::celsius())
+            .convert_to_with_factor(&Unit::fahrenheit())
+            .unwrap();
+        assert_relative_eq!(converted.unsafe_value().to_f64(), 50.0, epsilon = 1e-12);
+        assert_relative_eq!(factor.to_f64(), 1.8, epsilon = 1e-12);
+
+        assert!(Quantity::new_f64(1.0, Unit::meter())
+            .convert_to_with_factor(&Unit::second())
+            .is_err());
+    }
+
+    #[test]
+    fn dimension_mismatch() {
+        use crate::dimension::BaseDimension;
+
+        let base =
+            |name: &str, exponent| (BaseDimension(name.into()), Exponent::from_integer(exponent));
+
+        let q = Quantity::new_f64(1.0, Unit::meter());
+        assert_eq!(
+            q.dimension_mismatch(&Unit::second()),
+            Some((
+                Dimension::from_factors([base("meter", 1)]),
+                Dimension::from_factors([base("second", 1)])
+            ))
+        );
+        assert_eq!(q.dimension_mismatch(&Unit::kilometer()), None);
+        assert_eq!(
+            Quantity::new_f64(1.0, Unit::kph()).dimension_mismatch(&Unit::hertz()),
+            Some((
+                Dimension::from_factors([base("meter", 1), base("second", -1)]),
+                Dimension::from_factors([base("second", -1)])
+            ))
+        );
+    }
+
+    #[test]
+    fn ambiguous_base_units() {
+        let meter = Unit::new_base_with_dimension(
+            "meter",
+            CanonicalName::new("m", AcceptsPrefix::only_short()),
+            "Length",
+        );
+        let foot = Unit::new_base_with_dimension(
+            "foot",
+            CanonicalName::new("ft", AcceptsPrefix::none()),
+            "Length",
+        );
+        let second = Unit::new_base_with_dimension(
+            "second",
+            CanonicalName::new("s", AcceptsPrefix::only_short()),
+            "Time",
+        );
+
+        let q = Quantity::new_f64(1.0, meter.clone());
+        assert_eq!(
+            q.convert_to(&foot),
+            Err(QuantityError::AmbiguousBaseUnits(
+                meter.clone(),
+                foot.clone()
+            ))
+        );
+        assert_eq!(
+            Quantity::new_f64(1.0, meter.clone() / second.clone())
+                .convert_to(&(foot.clone() / second.clone())),
+            Err(QuantityError::AmbiguousBaseUnits(
+                meter.clone() / second.clone(),
+                foot.clone() / second.clone()
+            ))
+        );
+        assert_eq!(
+            q.convert_to(&second),
+            Err(QuantityError::IncompatibleUnits(meter, second))
+        );
+    }
+
+    #[test]
+    fn error_context() {
+        let error = (&Quantity::new_f64(1.0, Unit::meter())
+            + &Quantity::new_f64(1.0, Unit::second()))
+            .map_err(|e| e.with_context("add"))
+            .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "during add: Conversion error: unit 's' can not be converted to 'm'"
+        );
+
+        let error = error.with_context("sum");
+        assert_eq!(
+            error.to_string(),
+            "during sum: during add: Conversion error: unit 's' can not be converted to 'm'"
+        );
+        assert_eq!(
+            std::error::Error::source(&error).map(|e| e.to_string()),
+            Some("during add: Conversion error: unit 's' can not be converted to 'm'".into())
+        );
+    }
+
+    #[test]
+    fn hypot() {
+        let q = Quantity::new_f64(3.0, Unit::meter())
+            .hypot(&Quantity::new_f64(4.0, Unit::meter()))
+            .unwrap();
+        assert_eq!(q.unit(), &Unit::meter());
+        assert_eq!(q.unsafe_value().to_f64(), 5.0);
+
+        let q = Quantity::new_f64(3.0, Unit::meter())
+            .hypot(&Quantity::new_f64(400.0, Unit::centimeter()))
+            .unwrap();
+        assert_eq!(q.unit(), &Unit::meter());
+        assert_eq!(q.unsafe_value().to_f64(), 5.0);
+
+        let large = Quantity::new_f64(1e300, Unit::meter());
+        assert_eq!(
+            large.hypot(&large).unwrap().unsafe_value().to_f64(),
+            1e300 * std::f64::consts::SQRT_2
+        );
+
+        assert_eq!(
+            Quantity::new_f64(3.0, Unit::meter()).hypot(&Quantity::new_f64(4.0, Unit::second())),
+            Err(QuantityError::IncompatibleUnits(
+                Unit::second(),
+                Unit::meter()
+            ))
+        );
+        assert_eq!(
+            Quantity::new_f64(3.0, Unit::meter()).hypot(&Quantity::new_f64(0.0, Unit::second())),
+            Err(QuantityError::IncompatibleUnits(
+                Unit::second(),
+                Unit::meter()
+            ))
+        );
+    }
+
+    #[test]
+    fn approx_eq_within() {
+        let zero = Quantity::new_f64(0.0, Unit::meter());
+        let tiny = Quantity::new_f64(1e-12, Unit::meter());
+        assert!(zero.approx_eq_within(&tiny, 1e-9, 1e-10));
+        assert!(!zero.approx_eq_within(&tiny, 1e-9, 0.0));
+        assert!(!zero.approx_eq_within(&tiny, 1e-9, 1e-13));
+
+        let large = Quantity::new_f64(1e20, Unit::meter());
+        let larger = Quantity::new_f64(1e20 + 1e10, Unit::meter());
+        assert!(large.approx_eq_within(&larger, 1e-9, 0.0));
+        assert!(!large.approx_eq_within(&larger, 1e-11, 1.0));
+
+        let km = Quantity::new_f64(1.0, Unit::kilometer());
+        let m = Quantity::new_f64(1000.000001, Unit::meter());
+        assert!(km.approx_eq_within(&m, 1e-6, 0.0));
+        assert!(!km.approx_eq_within(&m, 1e-12, 0.0));
+
+        let s = Quantity::new_f64(0.0, Unit::second());
+        assert!(!zero.approx_eq_within(&s, 1.0, 1.0));
+    }
+
+    #[test]
+    fn is_integer_multiple_of() {
+        let minute = Quantity::new_f64(1.0, Unit::minute());
+        assert_eq!(
+            Quantity::new_f64(120.0, Unit::second()).is_integer_multiple_of(&minute, 1e-9),
+            Ok(true)
+        );
+        assert_eq!(
+            Quantity::new_f64(125.0, Unit::second()).is_integer_multiple_of(&minute, 1e-9),
+            Ok(false)
+        );
+        assert_eq!(
+            Quantity::new_f64(125.0, Unit::second()).is_integer_multiple_of(&minute, 0.1),
+            Ok(true)
+        );
+        assert_eq!(
+            Quantity::new_f64(-3.0, Unit::hour()).is_integer_multiple_of(&minute, 1e-9),
+            Ok(true)
+        );
+
+        assert!(Quantity::new_f64(120.0, Unit::meter())
+            .is_integer_multiple_of(&minute, 1e-9)
+            .is_err());
+        assert_eq!(
+            Quantity::new_f64(120.0, Unit::second())
+                .is_integer_multiple_of(&Quantity::new_f64(0.0, Unit::second()), 1e-9),
+            Err(QuantityError::DivisionByZero)
+        );
+    }
+
+    #[test]
+    fn trigonometric_functions() {
+        use approx::assert_relative_eq;
+
+        let right_angle = Quantity::new_f64(90.0, Unit::degree());
+        let sin = right_angle.sin().unwrap();
+        assert_eq!(sin.unit(), &Unit::scalar());
+        assert_relative_eq!(sin.unsafe_value().to_f64(), 1.0, epsilon = 1e-12);
+
+        assert_eq!(
+            Quantity::from_scalar(0.0).cos(),
+            Ok(Quantity::from_scalar(1.0))
+        );
+        assert_relative_eq!(
+            Quantity::new_f64(std::f64::consts::FRAC_PI_4, Unit::radian())
+                .tan()
+                .unwrap()
+                .unsafe_value()
+                .to_f64(),
+            1.0,
+            epsilon = 1e-12
+        );
+
+        assert_eq!(
+            Quantity::new_f64(1.0, Unit::meter()).sin(),
+            Err(QuantityError::IncompatibleUnits(
+                Unit::meter(),
+                Unit::scalar()
+            ))
+        );
+    }
+
+    #[test]
+    fn exp_and_logarithms() {
+        assert_eq!(
+            Quantity::from_scalar(1.0).ln(),
+            Ok(Quantity::from_scalar(0.0))
+        );
+        assert_eq!(
+            Quantity::from_scalar(0.0).exp(),
+            Ok(Quantity::from_scalar(1.0))
+        );
+        assert_eq!(
+            Quantity::from_scalar(1000.0).log10(),
+            Ok(Quantity::from_scalar(3.0))
+        );
+        assert_eq!(
+            Quantity::from_scalar(1024.0).log2(),
+            Ok(Quantity::from_scalar(10.0))
+        );
+        assert_eq!(
+            Quantity::new_f64(1.0, Unit::meter() / Unit::millimeter()).log10(),
+            Ok(Quantity::from_scalar(3.0))
+        );
+
+        assert_eq!(
+            Quantity::new_f64(5.0, Unit::meter()).ln(),
+            Err(QuantityError::IncompatibleUnits(
+                Unit::meter(),
+                Unit::scalar()
+            ))
+        );
+        assert!(Quantity::new_f64(5.0, Unit::second()).exp().is_err());
+    }
+
+    #[test]
+    fn decibels() {
+        use approx::assert_relative_eq;
+
+        let power = Quantity::new_f64(10.0, Unit::watt());
+        let reference = Quantity::new_f64(1.0, Unit::watt());
+        let decibels = power.to_decibels(&reference, true).unwrap();
+        assert_eq!(decibels, Number::from_f64(10.0));
+        assert_eq!(
+            Quantity::from_decibels(decibels, &reference, true),
+            Quantity::new_f64(10.0, Unit::watt())
+        );
+
+        let field = Quantity::new_f64(1.0, Unit::meter());
+        let reference = Quantity::new_f64(10.0, Unit::centimeter());
+        let decibels = field.to_decibels(&reference, false).unwrap();
+        assert_relative_eq!(decibels.to_f64(), 20.0, epsilon = 1e-12);
+        let recovered = Quantity::from_decibels(Number::from_f64(-20.0), &field, false);
+        assert_eq!(recovered.unit(), &Unit::meter());
+        assert_relative_eq!(recovered.unsafe_value().to_f64(), 0.1, epsilon = 1e-12);
+
+        assert!(power.to_decibels(&field, true).is_err());
+    }
+
+    #[test]
+    fn inverse_trigonometric_functions() {
+        use approx::assert_relative_eq;
+        use std::f64::consts::{FRAC_PI_2, FRAC_PI_4, PI};
+
+        let angle = Quantity::from_scalar(1.0).asin().unwrap();
+        assert_eq!(angle.unit(), &Unit::radian());
+        assert_relative_eq!(angle.unsafe_value().to_f64(), FRAC_PI_2, epsilon = 1e-12);
+        assert_relative_eq!(
+            angle
+                .convert_to(&Unit::degree())
+                .unwrap()
+                .unsafe_value()
+                .to_f64(),
+            90.0,
+            epsilon = 1e-12
+        );
+
+        assert_relative_eq!(
+            Quantity::from_scalar(-1.0)
+                .acos()
+                .unwrap()
+                .unsafe_value()
+                .to_f64(),
+            PI,
+            epsilon = 1e-12
+        );
+        assert_relative_eq!(
+            Quantity::new_f64(100.0, Unit::percent())
+                .atan()
+                .unwrap()
+                .unsafe_value()
+                .to_f64(),
+            FRAC_PI_4,
+            epsilon = 1e-12
+        );
+
+        let angle = Quantity::new_f64(1.0, Unit::meter())
+            .atan2(&Quantity::new_f64(100.0, Unit::centimeter()))
+            .unwrap();
+        assert_eq!(angle.unit(), &Unit::radian());
+        assert_relative_eq!(angle.unsafe_value().to_f64(), FRAC_PI_4, epsilon = 1e-12);
+
+        assert_eq!(
+            Quantity::new_f64(1.0, Unit::meter()).asin(),
+            Err(QuantityError::IncompatibleUnits(
+                Unit::meter(),
+                Unit::scalar()
+            ))
+        );
+        assert!(Quantity::new_f64(1.0, Unit::meter())
+            .atan2(&Quantity::new_f64(1.0, Unit::second()))
+            .is_err());
+    }
+
+    #[test]
+    fn try_power() {
+        let area = Quantity::new_f64(4.0, Unit::meter().powi(2));
+        let root = area.try_power(&Quantity::from_scalar(0.5)).unwrap();
+        assert_eq!(root.unit(), &Unit::meter());
+        assert_eq!(root.unsafe_value().to_f64(), 2.0);
+
+        let q = Quantity::new_f64(8.0, Unit::meter());
+        let root = q.try_power(&Quantity::from_scalar(1.0 / 3.0)).unwrap();
+        assert_eq!(root.unit(), &Unit::meter().power(Rational::new(1, 3)));
+        assert_eq!(
+            q.try_power(&Quantity::from_scalar(-2.0)).unwrap().unit(),
+            &Unit::meter().powi(-2)
+        );
+
+        assert_eq!(
+            q.try_power(&Quantity::from_scalar(std::f64::consts::PI)),
+            Err(QuantityError::NonRationalExponent)
+        );
+        assert_eq!(
+            q.try_power_with_max_denominator(&Quantity::from_scalar(0.001), 100),
+            Err(QuantityError::NonRationalExponent)
+        );
+        assert!(q
+            .try_power_with_max_denominator(&Quantity::from_scalar(0.001), 1000)
+            .is_ok());
+        assert!(q
+            .try_power(&Quantity::new_f64(2.0, Unit::second()))
+            .is_err());
+    }
+
+    #[test]
+    fn is_dimensionless() {
+        assert!(Quantity::from_scalar(2.0).is_dimensionless());
+        assert!(Quantity::new_f64(2.0, Unit::meter() / Unit::meter()).is_dimensionless());
+        assert!(Quantity::new_f64(2.0, Unit::kilometer() / Unit::meter()).is_dimensionless());
+        assert!(Quantity::new_f64(2.0, Unit::percent()).is_dimensionless());
+        assert!(Quantity::new_f64(2.0, Unit::radian()).is_dimensionless());
+        assert!(Quantity::new_f64(2.0, Unit::degree()).is_dimensionless());
+
+        assert!(!Quantity::new_f64(2.0, Unit::meter()).is_dimensionless());
+        assert!(!Quantity::new_f64(2.0, Unit::hertz()).is_dimensionless());
+    }
+
+    #[test]
+    fn conversion_between_prefixes() {
+        let microsecond = Unit::second().with_prefix(Prefix::micro());
+        let millisecond = Unit::second().with_prefix(Prefix::milli());
+        let square_millimeter = Unit::millimeter().powi(2);
+        let square_centimeter = Unit::centimeter().powi(2);
+
+        let cases = [
+            (Quantity::new_f64(2.5, Unit::kilometer()), Unit::meter()),
+            (Quantity::new_f64(1500.0, microsecond), millisecond),
+            (
+                Quantity::new_f64(250.0, square_millimeter),
+                square_centimeter,
+            ),
+        ];
+
+        for (q, target) in cases {
+            assert!(q.prefix_factors(&target).is_some());
+            assert_eq!(
+                q.convert_to(&target).unwrap(),
+                q.convert_to_via_base_units(&target).unwrap()
+            );
+        }
+
+        assert_eq!(
+            Quantity::new_f64(2.5, Unit::kilometer())
+                .convert_to(&Unit::meter())
+                .unwrap(),
+            Quantity::new_f64(2500.0, Unit::meter())
+        );
+
+        assert!(Quantity::new_f64(1.0, Unit::kilometer())
+            .prefix_factors(&Unit::mile())
+            .is_none());
+        assert!(Quantity::new_f64(1.0, Unit::meter().powi(2))
+            .prefix_factors(&Unit::meter())
+            .is_none());
+    }
+
+    #[test]
+    fn convert_all() {
+        use approx::assert_relative_eq;
+
+        let meter_base = Unit::new_base_with_dimension(
+            "meter_base",
+            CanonicalName::new("mb", AcceptsPrefix::none()),
+            "Length",
+        );
+        let quantities = [
+            Quantity::new_f64(2.0, Unit::kilometer()),
+            Quantity::new_f64(1.0, Unit::mile()),
+            Quantity::new_f64(3.0, Unit::second()),
+            Quantity::new_f64(12.0, Unit::foot()),
+            Quantity::new_f64(1.0, meter_base),
+            Quantity::new_f64(5.0, Unit::meter()),
+        ];
+
+        let results = Quantity::convert_all(&quantities, &Unit::meter());
+        assert_eq!(results.len(), quantities.len());
+        for (q, result) in quantities.iter().zip(&results) {
+            match (result, q.convert_to(&Unit::meter())) {
+                (Ok(converted), Ok(expected)) => {
+                    assert_eq!(converted.unit(), expected.unit());
+                    assert_relative_eq!(
+                        converted.unsafe_value().to_f64(),
+                        expected.unsafe_value().to_f64(),
+                        max_relative = 1e-12
+                    );
+                }
+                (Err(e), Err(expected)) => assert_eq!(e, &expected),
+                (result, expected) => panic!("{result:?} != {expected:?}"),
+            }
+        }
+
+        assert!(results[0].is_ok());
+        assert!(results[1].is_ok());
+        assert!(results[2].is_err());
+        assert!(results[3].is_ok());
+        assert!(results[4].is_err());
+        assert!(results[5].is_ok());
+    }
+
+    #[test]
+    fn repeated_conversions() {
+        let (from, to) = (
+            Unit::kilometer() / Unit::hour(),
+            Unit::mile() / Unit::hour(),
+        );
+
+        for i in 0..1_000 {
+            let value = i as f64;
+            let converted = Quantity::new_f64(value, from.clone())
+                .convert_to(&to)
+                .unwrap();
+            assert_eq!(converted.unit(), &to);
+
+            if i % 100 == 0 {
+                // Compare with a conversion between freshly created (uncached) units
+                let expected = Quantity::new_f64(value, Unit::kilometer() / Unit::hour())
+                    .convert_to(&(Unit::mile() / Unit::hour()))
+                    .unwrap();
+                assert_eq!(converted.unsafe_value(), expected.unsafe_value());
+            }
+        }
+    }
+
+    #[test]
+    fn to_mixed_radix() {
+        use approx::assert_relative_eq;
+
+        let feet_and_inches = [Unit::foot(), Unit::inch()];
+
+        let (counts, remainder) = Quantity::new_f64(6.25, Unit::foot())
+            .to_mixed_radix(&feet_and_inches)
+            .unwrap();
+        assert_eq!(counts, vec![(6, Unit::foot())]);
+        assert_eq!(remainder.unit(), &Unit::inch());
+        assert_relative_eq!(remainder.unsafe_value().to_f64(), 3.0, epsilon = 1e-12);
+
+        let (counts, remainder) = Quantity::new_f64(7.0, Unit::foot())
+            .to_mixed_radix(&feet_and_inches)
+            .unwrap();
+        assert_eq!(counts, vec![(7, Unit::foot())]);
+        assert_eq!(remainder.to_string(), "0 in");
+
+        let (counts, remainder) = Quantity::new_f64(-6.25, Unit::foot())
+            .to_mixed_radix(&feet_and_inches)
+            .unwrap();
+        assert_eq!(counts, vec![(-6, Unit::foot())]);
+        assert_relative_eq!(remainder.unsafe_value().to_f64(), 3.0, epsilon = 1e-12);
+
+        let (counts, remainder) = Quantity::new_f64(-3.5, Unit::inch())
+            .to_mixed_radix(&feet_and_inches)
+            .unwrap();
+        assert_eq!(counts, vec![(0, Unit::foot())]);
+        assert_eq!(remainder.to_string(), "-3.5 in");
+
+        let (counts, remainder) = Quantity::new_f64(5430.5, Unit::second())
+            .to_mixed_radix(&[Unit::hour(), Unit::minute(), Unit::second()])
+            .unwrap();
+        assert_eq!(counts, vec![(1, Unit::hour()), (30, Unit::minute())]);
+        assert_eq!(remainder.to_string(), "30.5 s");
+
+        assert!(Quantity::new_f64(1.0, Unit::hour())
+            .to_mixed_radix(&[Unit::hour(), Unit::meter()])
+            .is_err());
+    }
+
+    #[test]
+    fn to_compound() {
+        let parts = Quantity::new_f64(1.5, Unit::hour())
+            .to_compound(&[Unit::hour(), Unit::minute()])
+            .unwrap();
+        assert_eq!(
+            parts,
+            vec![
+                Quantity::new_f64(1.0, Unit::hour()),
+                Quantity::new_f64(30.0, Unit::minute())
+            ]
+        );
+        assert_eq!(parts[1].unit(), &Unit::minute());
+
+        let parts = Quantity::new_f64(5000.0, Unit::meter())
+            .to_compound(&[Unit::kilometer(), Unit::meter()])
+            .unwrap();
+        assert_eq!(parts[0].to_string(), "5 km");
+        assert_eq!(parts[1].to_string(), "0 m");
+
+        // Units are sorted by magnitude
+        let parts = Quantity::new_f64(5430.0, Unit::second())
+            .to_compound(&[Unit::second(), Unit::hour(), Unit::minute()])
+            .unwrap();
+        assert_eq!(
+            parts.iter().map(|q| q.to_string()).collect::<Vec<_>>(),
+            ["1 h", "30 min", "30 s"]
+        );
+
+        let parts = Quantity::new_f64(-90.0, Unit::minute())
+            .to_compound(&[Unit::hour(), Unit::minute()])
+            .unwrap();
+        assert_eq!(parts[0].to_string(), "-1 h");
+        assert_eq!(parts[1].to_string(), "-30 min");
+
+        // Exact boundaries are not affected by rounding errors
+        let parts = Quantity::new_f64(7200.0, Unit::second())
+            .to_compound(&[Unit::hour(), Unit::minute()])
+            .unwrap();
+        assert_eq!(parts[0].to_string(), "2 h");
+        assert_eq!(parts[1].to_string(), "0 min");
+        let parts = Quantity::new_f64(-7.0, Unit::foot())
+            .to_compound(&[Unit::foot(), Unit::inch()])
+            .unwrap();
+        assert_eq!(parts[0].to_string(), "-7 ft");
+        assert_eq!(parts[1].to_string(), "0 in");
+
+        assert!(Quantity::new_f64(1.0, Unit::hour())
+            .to_compound(&[Unit::hour(), Unit::meter()])
+            .is_err());
+        assert_eq!(
+            Quantity::new_f64(1.0, Unit::hour()).to_compound(&[]),
+            Ok(vec![])
+        );
+    }
+
+    #[test]
+    fn rational() {
+        let hour = Unit::new_derived(
+            "hour",
+            CanonicalName::new("h", AcceptsPrefix::none()),
+            Number::from_rational(Rational::from_integer(3600)),
+            Unit::second(),
+        );
+        let speed = Quantity::new(
+            Number::from_rational(Rational::from_integer(36)),
+            Unit::kilometer() / hour,
+        );
+
+        let converted = speed.convert_to(&(Unit::meter() / Unit::second())).unwrap();
+        assert_eq!(
+            converted.unsafe_value().to_rational(),
+            Some(Rational::from_integer(10))
+        );
+        assert_eq!(converted.to_string(), "10 m/s");
+
+        // Rational numbers are 64 bit wide, so conversions are exact for factors up
+        // to 10^18, and fall back to floating point numbers beyond that.
+        let one = Number::from_rational(Rational::from_integer(1));
+        let exameter = Quantity::new(one, Unit::meter().with_prefix(Prefix::Metric(18)));
+        assert_eq!(
+            exameter
+                .convert_to(&Unit::meter())
+                .unwrap()
+                .unsafe_value()
+                .to_rational(),
+            Some(Rational::from_integer(10i128.pow(18)))
+        );
+        let zettameter = Quantity::new(one, Unit::meter().with_prefix(Prefix::Metric(21)));
+        let converted = zettameter.convert_to(&Unit::meter()).unwrap();
+        assert_eq!(converted.unsafe_value().to_rational(), None);
+        assert_eq!(converted.unsafe_value().to_f64(), 1e21);
+    }
+
+    #[test]
+    fn complex() {
+        let i = Quantity::from_scalar(-1.0).sqrt().unwrap();
+        assert_eq!(
+            *i.unsafe_value(),
+            Number::Complex(num_complex::Complex64::i())
+        );
+        assert_eq!(i.to_string(), "i");
+        assert!(i.is_finite());
+
+        let q = Quantity::new_f64(-1.0, Unit::meter().powi(2))
+            .sqrt()
+            .unwrap();
+        assert_eq!(q.to_string(), "i m");
+        assert_eq!(
+            q.clone() * q.clone(),
+            Quantity::new_f64(-1.0, Unit::meter().powi(2))
+        );
+
+        let q = Quantity::new_f64(-4.0, Unit::meter().powi(2))
+            .power(Quantity::from_scalar(0.5))
+            .unwrap();
+        assert_eq!(q.to_string(), "2i m");
+        assert_eq!(
+            (&q + &Quantity::new_f64(1.0, Unit::meter()))
+                .unwrap()
+                .to_string(),
+            "(1 + 2i) m"
+        );
+        assert_eq!(
+            Quantity::new(
+                Number::from_complex(num_complex::Complex64::new(3.0, 4.0)),
+                Unit::meter()
+            )
+            .abs(),
+            Quantity::new_f64(5.0, Unit::meter())
+        );
+    }
+
+    #[test]
+    fn sqrt() {
+        let area = Quantity::new_f64(4.0, Unit::meter().powi(2));
+
+        let length = area.clone().sqrt().expect("square root succeeds");
+        assert_eq!(length, Quantity::new_f64(2.0, Unit::meter()));
+        assert_eq!(length.unit(), &Unit::meter());
+
+        let area_again = length
+            .power(Quantity::from_scalar(2.0))
+            .expect("exponent is scalar");
+        assert_eq!(area_again, area);
+    }
+
+    #[test]
+    fn cbrt() {
+        let volume = Quantity::new_f64(8.0, Unit::meter().powi(3));
+
+        let length = volume.cbrt().expect("cube root succeeds");
+        assert_eq!(length.unsafe_value().to_f64(), 2.0);
+        assert_eq!(length.unit(), &Unit::meter());
+
+        let unit_cube = Quantity::from_unit(Unit::meter().powi(3));
+        assert_eq!(
+            unit_cube.cbrt().expect("cube root succeeds").unit(),
+            &Unit::meter()
+        );
+
+        let interval = Quantity::new(Number::from_interval(1.0, 8.0), Unit::meter().powi(3));
+        let length = interval.cbrt().expect("cube root succeeds");
+        assert!(matches!(length.unsafe_value(), Number::Interval { .. }));
+        assert!(length.unsafe_value().to_f64() > 1.4 && length.unsafe_value().to_f64() < 1.6);
+    }
+
+    #[test]
+    fn abs() {
+        let q = Quantity::new_f64(-3.0, Unit::meter());
+        assert_eq!(q.abs(), Quantity::new_f64(3.0, Unit::meter()));
+
+        let unit = Unit::kilogram() / (Unit::meter() * Unit::second().powi(2));
+        let q = Quantity::new_f64(2.5, unit.clone());
+        let q_abs = q.clone().abs();
+        assert_eq!(q_abs, q);
+        assert_eq!(
+            q_abs.unit().iter().collect::<Vec<_>>(),
+            unit.iter().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn min_max() {
+        use approx::assert_relative_eq;
+
+        let km = Quantity::new_f64(1.0, Unit::kilometer());
+        let m = Quantity::new_f64(999.0, Unit::meter());
+
+        let min = km.clone().min(m.clone()).unwrap();
+        assert_eq!(min.unit(), &Unit::kilometer());
+        assert_relative_eq!(min.unsafe_value().to_f64(), 0.999, epsilon = 1e-12);
+
+        let max = km.clone().max(m.clone()).unwrap();
+        assert_eq!(max.unit(), &Unit::kilometer());
+        assert_eq!(max.unsafe_value().to_f64(), 1.0);
+
+        let min = m.clone().min(km.clone()).unwrap();
+        assert_eq!(min.unit(), &Unit::meter());
+        assert_eq!(min.unsafe_value().to_f64(), 999.0);
+
+        let max = m.clone().max(km.clone()).unwrap();
+        assert_eq!(max.unit(), &Unit::meter());
+        assert_eq!(max.unsafe_value().to_f64(), 1000.0);
+
+        let s = Quantity::new_f64(1.0, Unit::second());
+        assert_eq!(
+            km.clone().min(s.clone()),
+            Err(QuantityError::IncompatibleUnits(
+                Unit::second(),
+                Unit::kilometer()
+            ))
+        );
+        assert!(km.clone().max(s).is_err());
+
+        // Zero quantities of another dimension are not compatible either
+        let zero_seconds = Quantity::new_f64(0.0, Unit::second());
+        assert_eq!(
+            km.clone().min(zero_seconds.clone()),
+            Err(QuantityError::IncompatibleUnits(
+                Unit::second(),
+                Unit::kilometer()
+            ))
+        );
+        assert!(km.clone().max(zero_seconds).is_err());
+        assert_eq!(
+            km.min(Quantity::from_scalar(0.0)),
+            Ok(Quantity::new_f64(0.0, Unit::kilometer()))
+        );
+    }
+
+    #[test]
+    fn clamp() {
+        let q = Quantity::new_f64(5.0, Unit::meter());
+
+        let clamped = q
+            .clone()
+            .clamp(
+                Quantity::new_f64(0.0, Unit::meter()),
+                Quantity::new_f64(3.0, Unit::meter()),
+            )
+            .unwrap();
+        assert_eq!(clamped.unit(), &Unit::meter());
+        assert_eq!(clamped.unsafe_value().to_f64(), 3.0);
+
+        let clamped = q
+            .clone()
+            .clamp(
+                Quantity::new_f64(10.0, Unit::centimeter()),
+                Quantity::new_f64(2.0, Unit::meter()),
+            )
+            .unwrap();
+        assert_eq!(clamped.unit(), &Unit::meter());
+        assert_eq!(clamped.unsafe_value().to_f64(), 2.0);
+
+        assert!(q
+            .clone()
+            .clamp(
+                Quantity::new_f64(1.0, Unit::second()),
+                Quantity::new_f64(3.0, Unit::meter()),
+            )
+            .is_err());
+        assert!(q
+            .clone()
+            .clamp(
+                Quantity::new_f64(1.0, Unit::meter()),
+                Quantity::new_f64(3.0, Unit::second()),
+            )
+            .is_err());
+        assert_eq!(
+            q.clamp(
+                Quantity::new_f64(0.0, Unit::second()),
+                Quantity::new_f64(3.0, Unit::meter()),
+            ),
+            Err(QuantityError::IncompatibleUnits(
+                Unit::second(),
+                Unit::meter()
+            ))
+        );
+    }
+
+    #[test]
+    fn rem() {
+        let result = (&Quantity::new_f64(7.0, Unit::meter())
+            % &Quantity::new_f64(2.0, Unit::meter()))
+            .unwrap();
+        assert_eq!(result, Quantity::new_f64(1.0, Unit::meter()));
+
+        let result = (&Quantity::new_f64(90.0, Unit::degree())
+            % &Quantity::new_f64(360.0, Unit::degree()))
+            .unwrap();
+        assert_eq!(result, Quantity::new_f64(90.0, Unit::degree()));
+
+        let result = (&Quantity::new_f64(150.0, Unit::centimeter())
+            % &Quantity::new_f64(1.0, Unit::meter()))
+            .unwrap();
+        assert_eq!(result.unit(), &Unit::centimeter());
+        assert_eq!(result.unsafe_value().to_f64(), 50.0);
+
+        let result = (&Quantity::new_f64(7.0, Unit::meter())
+            % &Quantity::new_f64(0.0, Unit::meter()))
+            .unwrap();
+        assert!(result.unsafe_value().to_f64().is_nan());
+
+        assert!(
+            (&Quantity::new_f64(7.0, Unit::meter()) % &Quantity::new_f64(2.0, Unit::second()))
+                .is_err()
+        );
+        assert_eq!(
+            &Quantity::new_f64(7.0, Unit::meter()) % &Quantity::new_f64(0.0, Unit::second()),
+            Err(QuantityError::IncompatibleUnits(
+                Unit::second(),
+                Unit::meter()
+            ))
+        );
+    }
+
+    #[test]
+    fn lerp() {
+        let zero = Quantity::new_f64(0.0, Unit::meter());
+
+        let q = zero
+            .lerp(&Quantity::new_f64(10.0, Unit::meter()), 0.5)
+            .unwrap();
+        assert_eq!(q.unit(), &Unit::meter());
+        assert_eq!(q.unsafe_value().to_f64(), 5.0);
+
+        let q = zero
+            .lerp(&Quantity::new_f64(1.0, Unit::kilometer()), 0.5)
+            .unwrap();
+        assert_eq!(q.unit(), &Unit::meter());
+        assert_eq!(q.unsafe_value().to_f64(), 500.0);
+
+        let q = zero
+            .lerp(&Quantity::new_f64(10.0, Unit::meter()), 1.5)
+            .unwrap();
+        assert_eq!(q.unsafe_value().to_f64(), 15.0);
+
+        assert!(Quantity::new_f64(1.0, Unit::meter())
+            .lerp(&Quantity::new_f64(1.0, Unit::second()), 0.5)
+            .is_err());
+        assert!(Quantity::new_f64(1.0, Unit::meter())
+            .lerp(&Quantity::new_f64(0.0, Unit::second()), 0.5)
+            .is_err());
+    }
+
+    #[test]
+    fn add_assign_sub_assign() {
+        use approx::assert_relative_eq;
+
+        let mut acc = Quantity::new_f64(0.0, Unit::meter());
+        for q in [
+            Quantity::new_f64(1.0, Unit::meter()),
+            Quantity::new_f64(50.0, Unit::centimeter()),
+            Quantity::new_f64(0.2, Unit::meter()),
+        ] {
+            acc.add_assign(&q).unwrap();
+        }
+        assert_eq!(acc.unit(), &Unit::meter());
+        assert_relative_eq!(acc.unsafe_value().to_f64(), 1.7, epsilon = 1e-12);
+
+        acc.sub_assign(&Quantity::new_f64(70.0, Unit::centimeter()))
+            .unwrap();
+        assert_relative_eq!(acc.unsafe_value().to_f64(), 1.0, epsilon = 1e-12);
+
+        let before = acc.clone();
+        assert!(acc
+            .add_assign(&Quantity::new_f64(1.0, Unit::second()))
+            .is_err());
+        assert!(acc
+            .sub_assign(&Quantity::new_f64(1.0, Unit::second()))
+            .is_err());
+        assert_eq!(acc.unit(), before.unit());
+        assert_eq!(acc.unsafe_value(), before.unsafe_value());
+    }
+
+    #[test]
+    fn to_si_base() {
+        use approx::assert_relative_eq;
+
+        let kilowatt_hour = Unit::watt().with_prefix(Prefix::kilo()) * Unit::hour();
+        let q = Quantity::new_f64(1.0, kilowatt_hour).to_si_base();
+        assert_eq!(
+            q.unit(),
+            &(Unit::kilogram() * Unit::meter().powi(2) / Unit::second().powi(2))
+        );
+        assert_relative_eq!(q.unsafe_value().to_f64(), 3.6e6, max_relative = 1e-12);
+        assert!(q
+            .unit()
+            .iter()
+            .all(|f| f.unit_id.is_base()
+                && (f.prefix == Prefix::none() || f.unit_id.name() == "gram")));
+
+        let q = Quantity::new_f64(1.0, Unit::gram() / Unit::meter().powi(3)).to_si_base();
+        assert_eq!(q.unit(), &(Unit::kilogram() / Unit::meter().powi(3)));
+        assert_relative_eq!(q.unsafe_value().to_f64(), 1e-3, max_relative = 1e-12);
+
+        let q = Quantity::new_f64(1.0, Unit::kilometer() / Unit::hour()).to_si_base();
+        assert_eq!(q.unit(), &(Unit::meter() / Unit::second()));
+        assert_relative_eq!(q.unsafe_value().to_f64(), 1.0 / 3.6, max_relative = 1e-12);
+
+        let q = Quantity::new_f64(0.0, Unit::celsius()).to_si_base();
+        assert_eq!(q, Quantity::new_f64(273.15, Unit::kelvin()));
+    }
+
+    #[test]
+    fn trunc_and_fract_in() {
+        let q = Quantity::new_f64(1.25, Unit::hour());
+        assert_eq!(
+            q.fract_in(&Unit::hour()),
+            Ok(Quantity::new_f64(0.25, Unit::hour()))
+        );
+        assert_eq!(
+            q.trunc_in(&Unit::hour()),
+            Ok(Quantity::new_f64(1.0, Unit::hour()))
+        );
+        assert_eq!(
+            q.trunc_in(&Unit::minute()),
+            Ok(Quantity::new_f64(75.0, Unit::minute()))
+        );
+
+        let q = Quantity::new_f64(-90.0, Unit::minute());
+        assert_eq!(
+            q.trunc_in(&Unit::hour()),
+            Ok(Quantity::new_f64(-1.0, Unit::hour()))
+        );
+        assert_eq!(
+            q.fract_in(&Unit::hour()),
+            Ok(Quantity::new_f64(-0.5, Unit::hour()))
+        );
+
+        assert!(q.trunc_in(&Unit::meter()).is_err());
+    }
+
+    #[test]
+    fn try_into_f64() {
+        assert_eq!(f64::try_from(Quantity::from_scalar(3.0)), Ok(3.0));
+        assert_eq!(
+            f64::try_from(Quantity::new_f64(3.0, Unit::meter() / Unit::meter())),
+            Ok(3.0)
+        );
+        assert_eq!(
+            f64::try_from(Quantity::new_f64(50.0, Unit::percent())),
+            Ok(0.5)
+        );
+        assert_eq!(
+            f64::try_from(Quantity::new_f64(3.0, Unit::meter())),
+            Err(QuantityError::IncompatibleUnits(
+                Unit::meter(),
+                Unit::scalar()
+            ))
+        );
+        assert!(f64::try_from(Quantity::new_f64(0.0, Unit::meter())).is_err());
+
+        let value: Result<f64> =
+            Quantity::new_f64(2.0, Unit::kilometer() / Unit::meter()).try_into();
+        assert_eq!(value, Ok(2000.0));
+    }
+
+    #[test]
+    fn map_value() {
+        let clamp = |x: f64| x.clamp(0.0, 100.0);
+        assert_eq!(
+            Quantity::new_f64(120.0, Unit::kilometer()).map_value(clamp),
+            Quantity::new_f64(100.0, Unit::kilometer())
+        );
+        assert_eq!(
+            Quantity::new_f64(-5.0, Unit::second()).map_value(clamp),
+            Quantity::new_f64(0.0, Unit::second())
+        );
+
+        assert_eq!(
+            Quantity::new_f64(50.0, Unit::percent()).map_value_dimensionless(|x| x * 4.0),
+            Ok(Quantity::from_scalar(2.0))
+        );
+        assert!(Quantity::new_f64(1.0, Unit::meter())
+            .map_value_dimensionless(f64::sqrt)
+            .is_err());
+    }
+
+    #[test]
+    fn powi() {
+        let q = Quantity::new_f64(2.0, Unit::meter());
+        let cubed = q.clone().powi(3);
+        assert_eq!(cubed.unsafe_value().to_f64(), 8.0);
+        assert_eq!(cubed.unit(), &Unit::meter().powi(3));
+
+        let inverse = q.clone().powi(-1);
+        assert_eq!(inverse.unsafe_value().to_f64(), 0.5);
+        assert_eq!(inverse.unit(), &Unit::meter().powi(-1));
+
+        let one = q.powi(0);
+        assert_eq!(one.unsafe_value().to_f64(), 1.0);
+        assert_eq!(one.unit(), &Unit::scalar());
+
+        let rational = Quantity::new(Number::from_rational(Rational::new(2, 3)), Unit::second());
+        assert_eq!(
+            rational.powi(-2).unsafe_value().to_rational(),
+            Some(Rational::new(9, 4))
+        );
+    }
+
+    #[test]
+    fn sum_and_product() {
+        let lengths = [
+            Quantity::new_f64(1.0, Unit::meter()),
+            Quantity::new_f64(50.0, Unit::centimeter()),
+        ];
+        assert_eq!(
+            lengths.iter().cloned().sum::<Result<Quantity>>(),
+            Ok(Quantity::new_f64(1.5, Unit::meter()))
+        );
+        assert_eq!(
+            lengths.iter().rev().cloned().sum::<Result<Quantity>>(),
+            Ok(Quantity::new_f64(150.0, Unit::centimeter()))
+        );
+        assert_eq!(
+            [
+                Quantity::new_f64(0.0, Unit::meter()),
+                Quantity::new_f64(50.0, Unit::centimeter())
+            ]
+            .into_iter()
+            .sum::<Result<Quantity>>(),
+            Ok(Quantity::new_f64(0.5, Unit::meter()))
+        );
+        assert!([
+            Quantity::new_f64(1.0, Unit::meter()),
+            Quantity::new_f64(1.0, Unit::second())
+        ]
+        .into_iter()
+        .sum::<Result<Quantity>>()
+        .is_err());
+        assert_eq!(
+            std::iter::empty().sum::<Result<Quantity>>(),
+            Ok(Quantity::from_scalar(0.0))
+        );
+
+        let product: Quantity = [
+            Quantity::new_f64(2.0, Unit::meter()),
+            Quantity::new_f64(3.0, Unit::second()),
+        ]
+        .into_iter()
+        .product();
+        assert_eq!(product.unsafe_value().to_f64(), 6.0);
+        assert_eq!(product.unit(), &(Unit::meter() * Unit::second()));
+        assert_eq!(
+            std::iter::empty::<Quantity>().product::<Quantity>(),
+            Quantity::from_scalar(1.0)
+        );
+    }
+
+    #[test]
+    fn checked_arithmetic() {
+        let huge = Quantity::new_f64(1e200, Unit::meter());
+        assert_eq!(huge.checked_mul(&huge), Err(QuantityError::Overflow));
+        assert_eq!(
+            huge.try_div(&Quantity::new_f64(1e-200, Unit::second())),
+            Err(QuantityError::Overflow)
+        );
+
+        let max = Quantity::new_f64(f64::MAX, Unit::meter());
+        assert_eq!(max.checked_add(&max), Err(QuantityError::Overflow));
+        assert_eq!(max.checked_sub(&-max.clone()), Err(QuantityError::Overflow));
+
+        assert_eq!(
+            huge.checked_mul(&Quantity::new_f64(2.0, Unit::meter())),
+            Ok(Quantity::new_f64(2e200, Unit::meter().powi(2)))
+        );
+        assert_eq!(
+            Quantity::new_f64(1.0, Unit::kilometer())
+                .checked_add(&Quantity::new_f64(500.0, Unit::meter())),
+            Ok(Quantity::new_f64(1.5, Unit::kilometer()))
+        );
+        assert_eq!(
+            huge.try_div(&Quantity::new_f64(0.0, Unit::second())),
+            Err(QuantityError::DivisionByZero)
+        );
+        assert!(huge
+            .checked_add(&Quantity::new_f64(1.0, Unit::second()))
+            .is_err());
+
+        // Operands that are already infinite are not an overflow
+        let inf = Quantity::new_f64(f64::INFINITY, Unit::meter());
+        assert_eq!(inf.checked_mul(&huge), Ok(inf.clone() * huge.clone()));
+
+        // The operators themselves stay lenient
+        assert!((huge.clone() * huge.clone()).is_infinite());
+        assert!(huge
+            .clone()
+            .checked_div(Quantity::new_f64(1e-200, Unit::second()))
+            .is_some_and(|q| q.is_infinite()));
+        assert_eq!(
+            huge.checked_div(Quantity::new_f64(0.0, Unit::second())),
+            None
+        );
+    }
+
+    #[test]
+    fn mul_div_scalar() {
+        let unit = Unit::meter();
+        let q = Quantity::new_f64(2.0, unit.clone());
+
+        let tripled = q.clone().mul_scalar(3.0);
+        assert_eq!(tripled.unsafe_value().to_f64(), 6.0);
+        assert_eq!(
+            tripled.unit().iter().collect::<Vec<_>>(),
+            unit.iter().collect::<Vec<_>>()
+        );
+
+        assert_eq!(
+            q.clone().div_scalar(4.0),
+            Quantity::new_f64(0.5, Unit::meter())
+        );
+        assert!(q.div_scalar(0.0).unsafe_value().to_f64().is_infinite());
+    }
+
+    #[test]
+    fn signum() {
+        let q = Quantity::new_f64(-5.0, Unit::kilometer());
+        assert_eq!(q.signum(), Number::from_f64(-1.0));
+        assert!(!q.is_positive());
+        assert!(q.is_negative());
+
+        // Zero follows `f64::signum`
+        let q = Quantity::new_f64(0.0, Unit::meter());
+        assert_eq!(q.signum(), Number::from_f64(1.0));
+        assert!(!q.is_positive());
+        assert!(!q.is_negative());
+
+        let q = Quantity::new_f64(3.0, Unit::second());
+        assert_eq!(q.signum(), Number::from_f64(1.0));
+        assert!(q.is_positive());
+        assert!(!q.is_negative());
+    }
+
+    #[test]
+    fn reciprocal() {
+        let period = Quantity::new_f64(2.0, Unit::second());
+
+        let frequency = period.clone().reciprocal();
+        assert_eq!(frequency.unsafe_value().to_f64(), 0.5);
+        assert_eq!(frequency.unit(), &Unit::second().powi(-1));
+
+        let period_again = frequency.reciprocal();
+        assert_eq!(
+            period_again
+                .unit()
+                .canonicalized()
+                .iter()
+                .collect::<Vec<_>>(),
+            period.unit().canonicalized().iter().collect::<Vec<_>>()
+        );
+        assert_eq!(period_again, period);
+    }
+
+    #[test]
+    fn round_floor_ceil_to() {
+        let centimeter = Unit::centimeter();
+        let meter = Unit::meter();
+
+        let q = Quantity::new_f64(3.7, centimeter.clone());
+        let rounded = q.round_to(&centimeter).unwrap();
+        assert_eq!(rounded.unit(), &centimeter);
+        assert_eq!(rounded.unsafe_value().to_f64(), 4.0);
+
+        let q = Quantity::new_f64(1.9, meter.clone());
+        let floored = q.floor_to(&meter).unwrap();
+        assert_eq!(floored.unit(), &meter);
+        assert_eq!(floored.unsafe_value().to_f64(), 1.0);
+
+        let ceiled = q.ceil_to(&meter).unwrap();
+        assert_eq!(ceiled.unsafe_value().to_f64(), 2.0);
+
+        let rounded = q.round_to(&centimeter).unwrap();
+        assert_eq!(rounded.unit(), &centimeter);
+        assert_eq!(rounded.unsafe_value().to_f64(), 190.0);
+
+        assert!(q.round_to(&Unit::second()).is_err());
+        assert!(q.floor_to(&Unit::second()).is_err());
+        assert!(q.ceil_to(&Unit::second()).is_err());
+    }
+
+    #[test]
+    fn non_finite_values() {
+        let q = Quantity::new_f64(2.0, Unit::meter());
+        assert!(q.is_finite());
+        assert!(!q.is_nan());
+        assert!(!q.is_infinite());
+
+        let nan = Quantity::new_f64(0.0, Unit::meter()) / Quantity::new_f64(0.0, Unit::second());
+        assert!(!nan.is_finite());
+        assert!(nan.is_nan());
+        assert!(!nan.is_infinite());
+
+        let inf = q / Quantity::new_f64(0.0, Unit::second());
+        assert!(!inf.is_finite());
+        assert!(!inf.is_nan());
+        assert!(inf.is_infinite());
+    }
+
+    #[test]
+    fn value_in() {
+        let q = Quantity::new_f64(2.0, Unit::meter());
+        assert_eq!(q.value_in(&Unit::centimeter()), Ok(200.0));
+        assert_eq!(q.value_in(&Unit::meter()), Ok(2.0));
+        assert!(q.value_in(&Unit::second()).is_err());
+    }
+
+    #[test]
+    fn partial_cmp() {
+        use std::cmp::Ordering;
+
+        assert_eq!(
+            Quantity::new_f64(1.0, Unit::kilometer())
+                .partial_cmp(&Quantity::new_f64(999.0, Unit::meter())),
+            Some(Ordering::Greater)
+        );
+        assert_eq!(
+            Quantity::new_f64(1.0, Unit::hour())
+                .partial_cmp(&Quantity::new_f64(3601.0, Unit::second())),
+            Some(Ordering::Less)
+        );
+        assert_eq!(
+            Quantity::new_f64(1.0, Unit::meter())
+                .partial_cmp(&Quantity::new_f64(1.0, Unit::second())),
+            None
+        );
+
+        assert_eq!(
+            Quantity::new_f64(0.0, Unit::meter())
+                .partial_cmp(&Quantity::new_f64(0.0, Unit::second())),
+            None
+        );
+        assert_eq!(
+            Quantity::new_f64(5.0, Unit::meter()).partial_cmp(&Quantity::from_scalar(0.0)),
+            Some(Ordering::Greater)
+        );
+    }
+
+    #[test]
+    fn numerator_denominator() {
+        let q = Quantity::new_f64(3.0, Unit::kilogram() / Unit::second().powi(2));
+        assert_eq!(
+            q.numerator_denominator(),
+            (Unit::kilogram(), Unit::second().powi(2))
+        );
+        assert_eq!(
+            Quantity::from_scalar(2.0).numerator_denominator(),
+            (Unit::scalar(), Unit::scalar())
+        );
+    }
+
+    #[test]
+    fn percent_and_permille() {
+        let half = Quantity::new_f64(50.0, Unit::percent());
+        assert_eq!(
+            half.convert_to(&Unit::scalar()).unwrap(),
+            Quantity::from_scalar(0.5)
+        );
+        assert_eq!(half, Quantity::from_scalar(0.5));
+        assert_eq!(
+            Quantity::new_f64(5.0, Unit::permille())
+                .convert_to(&Unit::percent())
+                .unwrap(),
+            Quantity::new_f64(0.5, Unit::percent())
+        );
+        assert_eq!(
+            Quantity::from_scalar(0.25)
+                .convert_to(&Unit::percent())
+                .unwrap()
+                .to_string(),
+            "25%"
+        );
+    }
+
+    #[test]
+    fn ppm_and_ppb() {
+        let q = Quantity::new_f64(500.0, Unit::ppm());
+        assert_eq!(q.as_scalar().unwrap().to_f64(), 5e-4);
+        assert_eq!(Quantity::from_scalar(5e-4), q);
+        assert_eq!(q.to_string(), "500 ppm");
+
+        let lookup = |name: &str| match name {
+            "ppm" => Some(Unit::ppm()),
+            "ppb" => Some(Unit::ppb()),
+            _ => None,
+        };
+        assert_eq!(Quantity::parse(&q.to_string(), lookup), Ok(q.clone()));
+
+        let q_ppb = q.convert_to(&Unit::ppb()).unwrap();
+        assert_eq!(q_ppb.unit(), &Unit::ppb());
+        assert_eq!(q_ppb.unsafe_value().to_f64(), 500_000.0);
+        assert_eq!(q_ppb.to_string(), "500_000 ppb");
+    }
+
+    #[test]
+    fn turns_and_gradians() {
+        use approx::assert_relative_eq;
+
+        let one_turn = Quantity::new_f64(1.0, Unit::turn());
+        assert_eq!(
+            one_turn.convert_to(&Unit::degree()),
+            Ok(Quantity::new_f64(360.0, Unit::degree()))
+        );
+        assert_relative_eq!(
+            one_turn.value_in(&Unit::radian()).unwrap(),
+            std::f64::consts::TAU
+        );
+        assert_relative_eq!(
+            Quantity::new_f64(1.0, Unit::revolution())
+                .value_in(&Unit::turn())
+                .unwrap(),
+            1.0
+        );
+        assert_relative_eq!(
+            Quantity::new_f64(100.0, Unit::gradian())
+                .value_in(&Unit::degree())
+                .unwrap(),
+            90.0,
+            epsilon = 1e-12
+        );
+        assert_relative_eq!(
+            one_turn.value_in(&Unit::gradian()).unwrap(),
+            400.0,
+            epsilon = 1e-12
+        );
+
+        assert_eq!(
+            Quantity::new_f64(100.0, Unit::gradian()).to_string(),
+            "100 grad"
+        );
+        assert_eq!(Quantity::new_f64(2.0, Unit::turn()).to_string(), "2 turn");
+    }
+
+    #[test]
+    fn wrap_angle() {
+        use approx::assert_relative_eq;
+
+        let full_turn = Quantity::new_f64(360.0, Unit::degree());
+        let degrees = |value| Quantity::new_f64(value, Unit::degree());
+
+        assert_eq!(degrees(370.0).wrap_angle(&full_turn), Ok(degrees(10.0)));
+        assert_eq!(degrees(-90.0).wrap_angle(&full_turn), Ok(degrees(270.0)));
+        assert_eq!(degrees(360.0).wrap_angle(&full_turn), Ok(degrees(0.0)));
+
+        assert_eq!(
+            degrees(-90.0).wrap_symmetric(&full_turn),
+            Ok(degrees(-90.0))
+        );
+        assert_eq!(
+            degrees(270.0).wrap_symmetric(&full_turn),
+            Ok(degrees(-90.0))
+        );
+        assert_eq!(
+            degrees(180.0).wrap_symmetric(&full_turn),
+            Ok(degrees(-180.0))
+        );
+
+        let full_turn_radian = Quantity::new_f64(std::f64::consts::TAU, Unit::radian());
+        let wrapped = degrees(370.0).wrap_angle(&full_turn_radian).unwrap();
+        assert_eq!(wrapped.unit(), &Unit::degree());
+        assert_relative_eq!(wrapped.unsafe_value().to_f64(), 10.0, epsilon = 1e-9);
+
+        assert!(Quantity::new_f64(370.0, Unit::meter())
+            .wrap_angle(&full_turn)
+            .is_err());
+        assert!(degrees(370.0)
+            .wrap_angle(&Quantity::new_f64(360.0, Unit::second()))
+            .is_err());
+    }
+
+    #[test]
+    fn total_cmp() {
+        use std::cmp::Ordering;
+
+        let mut quantities = vec![
+            Quantity::new_f64(1.0, Unit::kilometer()),
+            Quantity::new_f64(500.0, Unit::meter()),
+            Quantity::new_f64(2000.0, Unit::centimeter()),
+        ];
+        quantities.sort_by(|a, b| a.total_cmp(b).unwrap());
+        assert_eq!(
+            quantities,
+            vec![
+                Quantity::new_f64(2000.0, Unit::centimeter()),
+                Quantity::new_f64(500.0, Unit::meter()),
+                Quantity::new_f64(1.0, Unit::kilometer()),
+            ]
+        );
+
+        let nan = Quantity::new_f64(f64::NAN, Unit::meter());
+        let mut quantities = [
+            Quantity::new_f64(1.0, Unit::kilometer()),
+            nan.clone(),
+            Quantity::new_f64(f64::INFINITY, Unit::centimeter()),
+            Quantity::new_f64(500.0, Unit::meter()),
+        ];
+        quantities.sort_by(|a, b| a.total_cmp(b).unwrap());
+        assert_eq!(quantities[0], Quantity::new_f64(500.0, Unit::meter()));
+        assert_eq!(quantities[1], Quantity::new_f64(1.0, Unit::kilometer()));
+        assert!(quantities[2].unsafe_value().to_f64().is_infinite());
+        assert!(quantities[3].is_nan());
+        assert_eq!(nan.total_cmp(&nan), Ok(Ordering::Equal));
+
+        assert!(Quantity::new_f64(1.0, Unit::meter())
+            .total_cmp(&Quantity::new_f64(1.0, Unit::second()))
+            .is_err());
+        assert!(Quantity::new_f64(1.0, Unit::meter())
+            .total_cmp(&Quantity::new_f64(0.0, Unit::second()))
+            .is_err());
+        assert_eq!(
+            Quantity::new_f64(1.0, Unit::meter()).total_cmp(&Quantity::from_scalar(0.0)),
+            Ok(Ordering::Greater)
+        );
+    }
+
+    #[test]
+    fn with_auto_prefix() {
+        let metric = PrefixSupport {
+            metric: true,
+            binary: false,
+        };
+        let meter = Unit::meter().with_prefix_support(metric);
+        let gram = Unit::gram().with_prefix_support(metric);
+
+        let q = Quantity::new_f64(3.4e-6, meter.clone()).with_auto_prefix();
+        assert_eq!(q.unit(), &Unit::meter().with_prefix(Prefix::micro()));
+        assert_eq!(q.to_string(), "3.4 µm");
+
+        let q = Quantity::new_f64(2500.0, gram).with_auto_prefix();
+        assert_eq!(q.unit(), &Unit::kilogram());
+        assert_eq!(q.to_string(), "2.5 kg");
+
+        let q =
+            Quantity::new_f64(0.5, meter.clone().with_prefix(Prefix::kilo())).with_auto_prefix();
+        assert_eq!(q.unit(), &Unit::meter());
+        assert_eq!(q.to_string(), "500 m");
+
+        let q = Quantity::new_f64(5.0, meter / Unit::second().with_prefix_support(metric));
+        assert_eq!(q.with_auto_prefix().unit(), q.unit());
+
+        let q = Quantity::new_f64(1234.0, Unit::degree());
+        assert_eq!(q.with_auto_prefix().unit(), q.unit());
+
+        // Units without `@metric_prefixes` are left alone, even if their symbol is a
+        // short alias like `h`
+        let q = Quantity::new_f64(7200.0, Unit::hour());
+        assert_eq!(q.with_auto_prefix().to_string(), "7200 h");
+        let q = Quantity::new_f64(3.4e-6, Unit::meter());
+        assert_eq!(q.with_auto_prefix().unit(), q.unit());
+    }
 
-impl Eq for Quantity {}
+    #[test]
+    fn with_auto_prefix_binary() {
+        let byte = Unit::byte().with_prefix_support(PrefixSupport {
+            metric: true,
+            binary: true,
+        });
+
+        // Metric prefixes are preferred for units without a prefix
+        let q = Quantity::new_f64(2048.0, byte.clone()).with_auto_prefix();
+        assert_eq!(q.to_string(), "2.048 kB");
+
+        // Binary prefixes are kept
+        let q =
+            Quantity::new_f64(2048.0, byte.clone().with_prefix(Prefix::kibi())).with_auto_prefix();
+        assert_eq!(q.unit(), &Unit::byte().with_prefix(Prefix::mebi()));
+        assert_eq!(q.to_string(), "2 MiB");
+
+        let q = Quantity::new_f64(0.5, byte.clone().with_prefix(Prefix::kibi())).with_auto_prefix();
+        assert_eq!(q.unit(), &Unit::byte());
+        assert_eq!(q.to_string(), "512 B");
+
+        // Units that only accept binary prefixes
+        let binary_byte = Unit::byte().with_prefix_support(PrefixSupport {
+            metric: false,
+            binary: true,
+        });
+        let q = Quantity::new_f64(2048.0, binary_byte).with_auto_prefix();
+        assert_eq!(q.to_string(), "2 KiB");
+    }
 
-impl PartialOrd for Quantity {
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        let other_converted = other.convert_to(self.unit()).ok()?;
-        self.value.partial_cmp(&other_converted.value)
+    #[test]
+    fn to_base_unit_representation() {
+        use approx::assert_relative_eq;
+
+        let q = Quantity::new_f64(1.0, Unit::kilometer() / Unit::hour());
+        let base = q.to_base_unit_representation();
+        assert_eq!(base.unit(), &(Unit::meter() / Unit::second()));
+        assert_relative_eq!(
+            base.unsafe_value().to_f64(),
+            1000.0 / 3600.0,
+            epsilon = 1e-12
+        );
+        assert_eq!(
+            base,
+            Quantity::new_f64(1000.0 / 3600.0, Unit::meter() / Unit::second())
+        );
     }
-}
 
-impl PrettyPrint for Quantity {
-    fn pretty_print(&self) -> crate::markup::Markup {
-        use crate::markup;
+    #[test]
+    fn hash() {
+        use std::collections::hash_map::DefaultHasher;
+        use std::collections::HashMap;
+        use std::hash::{Hash, Hasher};
+
+        let hash = |q: &Quantity| {
+            let mut hasher = DefaultHasher::new();
+            q.hash(&mut hasher);
+            hasher.finish()
+        };
 
-        let formatted_number = self.unsafe_value().pretty_print();
+        let kilometer_derived = Unit::new_derived(
+            "kilometer_derived",
+            CanonicalName::new("kmd", AcceptsPrefix::none()),
+            Number::from_f64(1000.0),
+            Unit::meter(),
+        );
 
-        let unit_str = format!("{}", self.unit());
+        let q1 = Quantity::new_f64(2.0, Unit::kilometer());
+        let q2 = Quantity::new_f64(2.0, kilometer_derived);
+        let q3 = Quantity::new_f64(2000.0, Unit::meter());
+        assert_eq!(q1, q2);
+        assert_eq!(hash(&q1), hash(&q2));
+        assert_eq!(hash(&q1), hash(&q3));
+        assert_ne!(hash(&q1), hash(&Quantity::new_f64(2.0, Unit::second())));
 
-        markup::value(formatted_number)
-            + if unit_str == "°" || unit_str.is_empty() {
-                markup::empty()
-            } else {
-                markup::space()
+        assert_eq!(
+            hash(&Quantity::new_f64(0.0, Unit::meter())),
+            hash(&Quantity::new_f64(-0.0, Unit::second()))
+        );
+
+        // Equality converts between units, as before
+        assert_eq!(
+            Quantity::new_f64(2.54, Unit::centimeter()),
+            Quantity::new_f64(1.0, Unit::inch())
+        );
+
+        // Equal quantities have equal hashes
+        let quantities = [
+            Quantity::new_f64(500.0, Unit::ppm()),
+            Quantity::from_scalar(5e-4),
+            Quantity::new_f64(1.0, Unit::inch()),
+            Quantity::new_f64(2.54, Unit::centimeter()),
+            Quantity::new_f64(0.0254, Unit::meter()),
+            Quantity::new_f64(0.0, Unit::celsius()),
+            Quantity::new_f64(273.15, Unit::kelvin()),
+            Quantity::new_f64(0.0, Unit::kelvin()),
+            q1.clone(),
+            q3.clone(),
+        ];
+        for a in &quantities {
+            for b in &quantities {
+                if a == b {
+                    assert_eq!(hash(a), hash(b), "{a} vs {b}");
+                }
             }
-            + markup::unit(unit_str)
+        }
+        assert_eq!(quantities[3], quantities[2]);
+        assert_eq!(quantities[5], quantities[6]);
+        assert_ne!(quantities[5], quantities[7]);
+
+        let mut map = HashMap::new();
+        map.insert(q1, "first");
+        map.insert(q3, "second");
+        assert_eq!(map.len(), 1);
+        assert_eq!(map[&Quantity::new_f64(2.0, Unit::kilometer())], "second");
     }
-}
 
-impl std::fmt::Display for Quantity {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        use crate::markup::{Formatter, PlainTextFormatter};
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_roundtrip() {
+        let roundtrip = |q: &Quantity| -> Quantity {
+            let json = serde_json::to_string(q).expect("serialization succeeds");
+            serde_json::from_str(&json).expect("deserialization succeeds")
+        };
 
-        let markup = self.pretty_print();
-        let formatter = PlainTextFormatter {};
-        write!(f, "{}", formatter.format(&markup, false).trim())
-    }
-}
+        let acceleration = Quantity::new_f64(9.81, Unit::meter() / Unit::second().powi(2));
+        let acceleration_roundtrip = roundtrip(&acceleration);
+        assert_eq!(acceleration_roundtrip, acceleration);
+        assert_eq!(acceleration_roundtrip.unit(), acceleration.unit());
+        assert_eq!(acceleration_roundtrip.to_string(), "9.81 m/s²");
 
-#[cfg(test)]
-mod tests {
-    use crate::{prefix::Prefix, prefix_parser::AcceptsPrefix, unit::CanonicalName};
+        let torque = Quantity::new_f64(2.5, Unit::newton() * Unit::meter());
+        let torque_roundtrip = roundtrip(&torque);
+        assert_eq!(torque_roundtrip, torque);
+        assert_eq!(torque_roundtrip.to_string(), "2.5 N·m");
 
-    use super::*;
+        let temperature = Quantity::new_f64(20.0, Unit::celsius());
+        assert_eq!(roundtrip(&temperature), temperature);
+
+        let data = Quantity::new_f64(3.0, Unit::byte().with_prefix(Prefix::kibi()));
+        assert_eq!(roundtrip(&data).to_string(), "3 KiB");
+    }
 
+    #[cfg(feature = "approx")]
     #[test]
-    fn conversion_trivial() {
-        let meter = Unit::meter();
-        let second = Unit::second();
+    fn approx_eq() {
+        use approx::{assert_relative_eq, relative_eq, AbsDiffEq};
 
-        let length = Quantity::new_f64(2.0, meter.clone());
+        let km = Quantity::new_f64(1.0, Unit::kilometer());
+        let km_in_m = km.convert_to(&Unit::meter()).unwrap();
+        assert_relative_eq!(km_in_m, Quantity::new_f64(1000.0, Unit::meter()));
+        assert_relative_eq!(km, Quantity::new_f64(1000.0, Unit::meter()));
+        assert_relative_eq!(
+            Quantity::new_f64(0.1, Unit::meter()).mul_scalar(3.0),
+            Quantity::new_f64(30.0, Unit::centimeter()),
+            epsilon = 1e-12
+        );
 
-        assert!(length.convert_to(&meter).is_ok());
+        let m = Quantity::new_f64(1.0, Unit::meter());
+        let s = Quantity::new_f64(1.0, Unit::second());
+        assert!(!relative_eq!(m, s, epsilon = f64::MAX));
+        assert!(!m.abs_diff_eq(&s, f64::MAX));
 
-        assert!(length.convert_to(&second).is_err());
-        assert!(length.convert_to(&Unit::scalar()).is_err());
+        let zero_m = Quantity::new_f64(0.0, Unit::meter());
+        let zero_s = Quantity::new_f64(0.0, Unit::second());
+        assert!(!zero_m.abs_diff_eq(&zero_s, f64::MAX));
     }
 
     #[test]
-    fn conversion_basic() {
+    fn affine_conversion() {
         use approx::assert_relative_eq;
 
-        let meter = Unit::meter();
-        let foot = Unit::new_derived(
-            "foot",
-            CanonicalName::new("ft", AcceptsPrefix::none()),
-            Number::from_f64(0.3048),
-            meter.clone(),
-        );
-
-        let length = Quantity::new_f64(2.0, meter.clone());
+        let celsius = Unit::celsius();
+        let fahrenheit = Unit::fahrenheit();
+        let kelvin = Unit::kelvin();
 
-        let length_in_foot = length.convert_to(&foot).expect("conversion succeeds");
-        assert_eq!(length_in_foot.unsafe_value().to_f64(), 2.0 / 0.3048);
+        assert_eq!(
+            Quantity::new_f64(0.0, celsius.clone()),
+            Quantity::new_f64(273.15, kelvin.clone())
+        );
+        assert_eq!(
+            Quantity::new_f64(273.15, kelvin.clone()),
+            Quantity::new_f64(0.0, celsius.clone())
+        );
 
-        let length_converted_back_to_meter = length_in_foot
-            .convert_to(&meter)
-            .expect("conversion succeeds");
+        let freezing = Quantity::new_f64(32.0, fahrenheit.clone());
+        assert_relative_eq!(freezing.value_in(&celsius).unwrap(), 0.0, epsilon = 1e-12);
         assert_relative_eq!(
-            length_converted_back_to_meter.unsafe_value().to_f64(),
-            2.0,
-            epsilon = 1e-6
+            Quantity::new_f64(100.0, celsius.clone())
+                .value_in(&fahrenheit)
+                .unwrap(),
+            212.0,
+            epsilon = 1e-12
         );
+
+        assert!(Quantity::new_f64(0.0, celsius.clone())
+            .convert_to(&Unit::meter())
+            .is_err());
     }
 
     #[test]
-    fn prefixes() {
-        use crate::prefix::Prefix;
+    fn affine_arithmetic() {
+        use approx::assert_relative_eq;
+
+        let difference = (&Quantity::new_f64(20.0, Unit::celsius())
+            - &Quantity::new_f64(10.0, Unit::celsius()))
+            .unwrap();
+        assert_eq!(difference.unit(), &Unit::kelvin());
+        assert_relative_eq!(difference.unsafe_value().to_f64(), 10.0, epsilon = 1e-12);
+
+        let sum = (&Quantity::new_f64(0.0, Unit::celsius())
+            + &Quantity::new_f64(10.0, Unit::kelvin()))
+            .unwrap();
+        assert_eq!(sum.unit(), &Unit::kelvin());
+        assert_relative_eq!(sum.unsafe_value().to_f64(), 283.15, epsilon = 1e-12);
+
+        let product = Quantity::from_scalar(2.0) * Quantity::new_f64(0.0, Unit::celsius());
+        assert_eq!(product, Quantity::new_f64(546.3, Unit::kelvin()));
+    }
 
+    #[test]
+    fn affine_arithmetic_is_consistent() {
         use approx::assert_relative_eq;
 
-        let meter = Unit::meter();
-        let centimeter = Unit::meter().with_prefix(Prefix::centi());
+        let twenty = Quantity::new_f64(20.0, Unit::celsius());
+        let ten = Quantity::new_f64(10.0, Unit::celsius());
 
-        let length = Quantity::new_f64(2.5, meter.clone());
-        {
-            let length_in_centimeter = length.convert_to(&centimeter).expect("conversion succeeds");
-            assert_relative_eq!(
-                length_in_centimeter.unsafe_value().to_f64(),
-                250.0,
-                epsilon = 1e-6
-            );
+        let sum = (&twenty + &ten).unwrap();
+        assert_eq!(sum.unit(), &Unit::kelvin());
+        assert_relative_eq!(sum.unsafe_value().to_f64(), 576.3, epsilon = 1e-9);
 
-            let length_converted_back_to_meter = length_in_centimeter
-                .convert_to(&meter)
-                .expect("conversion succeeds");
-            assert_relative_eq!(
-                length_converted_back_to_meter.unsafe_value().to_f64(),
-                2.5,
-                epsilon = 1e-6
-            );
-        }
-        {
-            let volume = length
-                .power(Quantity::from_scalar(3.0))
-                .expect("exponent is scalar");
+        let mut accumulator = twenty.clone();
+        accumulator.add_assign(&ten).unwrap();
+        assert_eq!(accumulator.unit(), sum.unit());
+        assert_eq!(accumulator.unsafe_value(), sum.unsafe_value());
 
-            let volume_in_centimeter3 = volume
-                .convert_to(&centimeter.powi(3))
-                .expect("conversion succeeds");
-            assert_relative_eq!(
-                volume_in_centimeter3.unsafe_value().to_f64(),
-                15_625_000.0,
-                epsilon = 1e-6
-            );
-        }
+        assert_eq!(
+            [twenty.clone(), ten.clone()]
+                .into_iter()
+                .sum::<Result<Quantity>>(),
+            Ok(sum)
+        );
+
+        let difference = (&twenty - &ten).unwrap();
+        let mut accumulator = twenty;
+        accumulator.sub_assign(&ten).unwrap();
+        assert_eq!(accumulator.unit(), &Unit::kelvin());
+        assert_eq!(accumulator.unsafe_value(), difference.unsafe_value());
+        assert_relative_eq!(difference.unsafe_value().to_f64(), 10.0, epsilon = 1e-9);
     }
 
     #[test]
@@ -451,6 +3410,20 @@ mod tests {
         assert_eq!(q.full_simplify(), q);
     }
 
+    #[test]
+    fn full_simplify_single_factor() {
+        for unit in [
+            Unit::kilometer(),
+            Unit::millimeter().powi(2),
+            Unit::hour().powi(-1),
+        ] {
+            let q = Quantity::new_f64(5.0, unit.clone());
+            let simplified = q.full_simplify();
+            assert_eq!(simplified.unit(), &unit);
+            assert_eq!(simplified.unsafe_value(), &Number::from_f64(5.0));
+        }
+    }
+
     #[test]
     fn full_simplify_convertible_to_scalar() {
         {
@@ -491,6 +3464,56 @@ mod tests {
         }
     }
 
+    #[test]
+    fn simplify_preferring() {
+        let preferred = [Unit::watt(), Unit::joule()];
+
+        let q = Quantity::new_f64(
+            1.0,
+            Unit::kilogram() * Unit::meter().powi(2) / Unit::second().powi(2),
+        );
+        let simplified = q.simplify_preferring(&preferred);
+        assert_eq!(simplified.unit(), &Unit::joule());
+        assert_eq!(simplified.unsafe_value().to_f64(), 1.0);
+
+        let q = Quantity::new_f64(1.0, Unit::joule() / Unit::second());
+        let simplified = q.simplify_preferring(&preferred);
+        assert_eq!(simplified.unit(), &Unit::watt());
+        assert_eq!(simplified.unsafe_value().to_f64(), 1.0);
+
+        // Nothing matches: same result as full_simplify
+        let q = Quantity::new_f64(2.0, Unit::meter() * Unit::second() * Unit::meter());
+        assert_eq!(
+            q.simplify_preferring(&preferred).unit(),
+            q.full_simplify().unit()
+        );
+        assert_eq!(q.simplify_preferring(&[]).unit(), q.full_simplify().unit());
+    }
+
+    #[test]
+    fn merge_same_dimension() {
+        let q = Quantity::new_f64(1.0, Unit::meter() * Unit::gram() / Unit::centimeter());
+        let merged = q.merge_same_dimension();
+        assert_eq!(merged.unit(), &Unit::gram());
+        assert_eq!(merged.unsafe_value().to_f64(), 100.0);
+        assert_eq!(merged.unit(), q.full_simplify().unit());
+
+        let q = Quantity::new_f64(2.0, Unit::meter() * Unit::second() * Unit::millimeter());
+        let merged = q.merge_same_dimension();
+        assert_eq!(merged.unit(), &(Unit::meter().powi(2) * Unit::second()));
+        assert_eq!(merged.unsafe_value().to_f64(), 0.002);
+
+        let q = Quantity::new_f64(2.0, Unit::meter() / Unit::second());
+        assert_eq!(q.merge_same_dimension().unit(), q.unit());
+
+        // Dimensionless units are not collapsed to scalars, unlike in full_simplify
+        let q = Quantity::new_f64(3.0, Unit::percent() * Unit::kilogram());
+        let merged = q.merge_same_dimension();
+        assert_eq!(merged.unit(), &(Unit::percent() * Unit::kilogram()));
+        assert_eq!(merged.unsafe_value().to_f64(), 3.0);
+        assert_eq!(q.full_simplify().unit(), &Unit::kilogram());
+    }
+
     #[test]
     fn full_simplify_scalarlike_units() {
         {
@@ -560,6 +3583,10 @@ mod tests {
         // number with no intervening space.
         assert_eq!(Quantity::new_f64(90.0, Unit::degree()).to_string(), "90°");
 
+        // The same applies to percent and permille signs in Numbat.
+        assert_eq!(Quantity::new_f64(50.0, Unit::percent()).to_string(), "50%");
+        assert_eq!(Quantity::new_f64(2.5, Unit::permille()).to_string(), "2.5‰");
+
         // A prefix is part of the unit, and its symbol is prepended to the
         // unit symbol without a separator (e.g., k in km, M in MPa, G in GHz).
         // Compound prefixes are not allowed.
@@ -593,4 +3620,216 @@ mod tests {
             "1 kg/(m·s²)"
         );
     }
+
+    #[test]
+    fn pretty_printing_with_precision() {
+        use crate::markup::{Formatter, PlainTextFormatter};
+
+        let format = |q: Quantity, sig_figs: usize| {
+            PlainTextFormatter {}.format(&q.pretty_print_with_precision(sig_figs), false)
+        };
+
+        assert_eq!(
+            format(Quantity::new_f64(123.456, Unit::meter()), 3),
+            "123 m"
+        );
+        assert_eq!(
+            format(Quantity::new_f64(0.0012345, Unit::second()), 3),
+            "0.00123 s"
+        );
+        assert_eq!(format(Quantity::new_f64(1.5, Unit::meter()), 3), "1.50 m");
+        assert_eq!(format(Quantity::new_f64(9.996, Unit::meter()), 3), "10.0 m");
+        assert_eq!(
+            format(Quantity::new_f64(123456.0, Unit::meter()), 3),
+            "123000 m"
+        );
+        assert_eq!(
+            format(Quantity::new_f64(-123.456, Unit::meter()), 4),
+            "-123.5 m"
+        );
+        assert_eq!(format(Quantity::new_f64(0.0, Unit::meter()), 3), "0.00 m");
+        assert_eq!(format(Quantity::new_f64(42.0, Unit::degree()), 1), "40°");
+        assert_eq!(format(Quantity::from_scalar(2.0), 2), "2.0");
+    }
+
+    #[test]
+    fn markdown() {
+        assert_eq!(
+            Quantity::new_f64(1.0, Unit::meter() / Unit::meter()).to_markdown(),
+            r"$1\,\mathrm{m}/\mathrm{m}$"
+        );
+        assert_eq!(Quantity::from_scalar(5.0).to_markdown(), "$5$");
+    }
+
+    #[test]
+    fn ascii() {
+        assert_eq!(
+            Quantity::new_f64(9.81, Unit::meter() / Unit::second().powi(2)).to_ascii(),
+            "9.81 m/s^2"
+        );
+        assert_eq!(
+            Quantity::new_f64(5.0, Unit::meter().with_prefix(Prefix::micro())).to_ascii(),
+            "5 um"
+        );
+        assert_eq!(Quantity::new_f64(90.0, Unit::degree()).to_ascii(), "90 deg");
+        assert_eq!(
+            Quantity::new_f64(1.0, Unit::newton() * Unit::meter()).to_ascii(),
+            "1 N*m"
+        );
+
+        use crate::markup::{AsciiFormatter, Formatter};
+        assert_eq!(
+            AsciiFormatter {}.format(
+                &Quantity::new_f64(0.00012, Unit::meter()).pretty_print_engineering(),
+                false
+            ),
+            "120e-6 m"
+        );
+    }
+
+    #[cfg(feature = "html-formatter")]
+    #[test]
+    fn html() {
+        assert_eq!(
+            Quantity::new_f64(9.81, Unit::meter() / Unit::second().powi(2)).to_html(),
+            "<span class=\"numbat-value\">9.81</span> <span class=\"numbat-unit\">m/s<sup>2</sup></span>"
+        );
+        assert_eq!(
+            Quantity::new_f64(90.0, Unit::degree()).to_html(),
+            "<span class=\"numbat-value\">90</span><span class=\"numbat-unit\">°</span>"
+        );
+    }
+
+    #[test]
+    fn latex() {
+        assert_eq!(
+            Quantity::new_f64(1.0, Unit::newton() * Unit::meter()).to_latex(),
+            r"1\,\mathrm{N}\cdot\mathrm{m}"
+        );
+        assert_eq!(
+            Quantity::new_f64(9.81, Unit::meter() / Unit::second().powi(2)).to_latex(),
+            r"9.81\,\mathrm{m}/\mathrm{s}^{2}"
+        );
+        assert_eq!(
+            Quantity::new_f64(
+                1.0,
+                Unit::kilogram() / (Unit::meter() * Unit::second().powi(2))
+            )
+            .to_latex(),
+            r"1\,\mathrm{kg}/(\mathrm{m}\cdot\mathrm{s}^{2})"
+        );
+        assert_eq!(
+            Quantity::new_f64(5.0, Unit::meter().with_prefix(Prefix::micro())).to_latex(),
+            r"5\,\mathrm{\mu m}"
+        );
+        assert_eq!(
+            Quantity::new_f64(90.0, Unit::degree()).to_latex(),
+            r"90^\circ"
+        );
+        assert_eq!(
+            Quantity::new_f64(2.0, Unit::meter().powi(-8)).to_latex(),
+            r"2\,\mathrm{m}^{-8}"
+        );
+        assert_eq!(
+            Quantity::new_f64(1.5e-10, Unit::second()).to_latex(),
+            r"1.5\times 10^{-10}\,\mathrm{s}"
+        );
+        assert_eq!(
+            Quantity::new_f64(1234567.0, Unit::second()).to_latex(),
+            r"1\,234\,567\,\mathrm{s}"
+        );
+        assert_eq!(Quantity::from_scalar(5.0).to_latex(), "5");
+    }
+
+    #[test]
+    fn scientific_pretty_printing() {
+        use crate::markup::{Formatter, PlainTextFormatter};
+
+        let format = |q: Quantity, digits| {
+            PlainTextFormatter {}.format(&q.pretty_print_scientific(digits), false)
+        };
+
+        assert_eq!(
+            format(Quantity::new_f64(730.0, Unit::meter().powi(2)), 1),
+            "7.3×10² m²"
+        );
+        assert_eq!(
+            format(Quantity::new_f64(0.001, Unit::second()), 1),
+            "1.0×10⁻³ s"
+        );
+        assert_eq!(
+            format(Quantity::new_f64(6.02214076e23, Unit::second().powi(-1)), 2),
+            "6.02×10²³ s⁻¹"
+        );
+        assert_eq!(
+            format(Quantity::new_f64(-9.996, Unit::meter()), 2),
+            "-1.00×10¹ m"
+        );
+        assert_eq!(
+            format(Quantity::new_f64(2.0, Unit::meter()), 1),
+            "2.0×10⁰ m"
+        );
+        assert_eq!(format(Quantity::new_f64(0.0, Unit::meter()), 0), "0×10⁰ m");
+        assert_eq!(
+            format(Quantity::new_f64(1500.0, Unit::degree()), 1),
+            "1.5×10³°"
+        );
+        assert_eq!(format(Quantity::from_scalar(123.0), 0), "1×10²");
+        assert_eq!(
+            format(Quantity::new_f64(f64::INFINITY, Unit::meter()), 1),
+            "inf m"
+        );
+    }
+
+    #[test]
+    fn engineering_pretty_printing() {
+        use crate::markup::{Formatter, PlainTextFormatter};
+
+        let format =
+            |q: Quantity| PlainTextFormatter {}.format(&q.pretty_print_engineering(), false);
+
+        assert_eq!(format(Quantity::new_f64(2.21, Unit::kilogram())), "2.21 kg");
+        assert_eq!(format(Quantity::new_f64(22.0, Unit::kelvin())), "22 K");
+        assert_eq!(
+            format(Quantity::new_f64(730.0, Unit::meter().powi(2))),
+            "730 m²"
+        );
+        assert_eq!(
+            format(Quantity::new_f64(7300.0, Unit::meter().powi(2))),
+            "7.3×10³ m²"
+        );
+        assert_eq!(format(Quantity::new_f64(90.0, Unit::degree())), "90°");
+        assert_eq!(format(Quantity::new_f64(9000.0, Unit::degree())), "9×10³°");
+        assert_eq!(
+            format(Quantity::new_f64(
+                1.0,
+                Unit::hertz().with_prefix(Prefix::giga())
+            )),
+            "1 GHz"
+        );
+        assert_eq!(
+            format(Quantity::new_f64(1.0, Unit::newton() * Unit::meter())),
+            "1 N·m"
+        );
+        assert_eq!(
+            format(Quantity::new_f64(1.0, Unit::meter() / Unit::meter())),
+            "1 m/m"
+        );
+        assert_eq!(
+            format(Quantity::new_f64(
+                1.0,
+                Unit::kilogram() / (Unit::meter() * Unit::second().powi(2))
+            )),
+            "1 kg/(m·s²)"
+        );
+        assert_eq!(
+            format(Quantity::new_f64(0.00012, Unit::second())),
+            "120×10⁻⁶ s"
+        );
+        assert_eq!(
+            format(Quantity::new_f64(-12345.0, Unit::meter())),
+            "-12.345×10³ m"
+        );
+        assert_eq!(format(Quantity::from_scalar(0.0)), "0");
+    }
 }
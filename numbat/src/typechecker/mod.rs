@@ -315,7 +315,7 @@ impl TypeChecker {
 
                 typed_ast::Expression::UnitIdentifier(
                     *span,
-                    *prefix,
+                    prefix.clone(),
                     name.clone(),
                     full_name.clone(),
                     TypeScheme::concrete(qt.inner),
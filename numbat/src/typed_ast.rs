@@ -734,6 +734,13 @@ fn decorator_markup(decorators: &Vec<Decorator>) -> Markup {
                         + m::string(description)
                         + m::operator(")")
                 }
+                Decorator::Deprecated(None) => m::decorator("@deprecated"),
+                Decorator::Deprecated(Some(suggestion)) => {
+                    m::decorator("@deprecated")
+                        + m::operator("(")
+                        + m::string(suggestion)
+                        + m::operator(")")
+                }
             }
             + m::nl();
     }
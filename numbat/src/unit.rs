@@ -1,27 +1,95 @@
-use std::{fmt::Display, ops::Div};
+use std::{
+    fmt::Display,
+    ops::Div,
+    sync::{Arc, OnceLock},
+};
 
 use itertools::Itertools;
-use num_traits::{ToPrimitive, Zero};
+use num_traits::Zero;
 
 use crate::{
-    arithmetic::{pretty_exponent, Exponent, Power, Rational},
+    arithmetic::{ascii_exponent, pretty_exponent, Exponent, Power, Rational},
+    dimension::{BaseDimension, Dimension},
     number::Number,
     prefix::Prefix,
     prefix_parser::AcceptsPrefix,
     product::{Canonicalize, Product},
+    quantity::QuantityError,
 };
 
 pub type ConversionFactor = Number;
 
 /// A unit can either be a base/fundamental unit or it is derived from another unit.
 /// In the latter case, a conversion factor to the defining unit has to be specified.
-#[derive(Debug, Clone, PartialEq, Eq)]
+///
+/// Affine units (like degree Celsius) additionally have an offset, i.e. a value `x`
+/// in that unit corresponds to `x · scale + offset` in the defining unit. The offset
+/// is only applied when converting a quantity whose unit is a single affine unit
+/// factor. Within compound units like `°C/m`, affine units act like derived units
+/// with their scale as conversion factor (they describe temperature differences).
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone)]
 pub enum UnitKind {
-    Base,
+    /// A base unit, along with the name of its physical dimension (like `Length`),
+    /// if known. Different base units of the same dimension can not be converted
+    /// into each other. The dimension is ignored when comparing units.
+    Base(Option<String>),
     Derived(ConversionFactor, Unit),
+    Affine {
+        scale: ConversionFactor,
+        offset: Number,
+        base_unit: Unit,
+    },
+}
+
+impl PartialEq for UnitKind {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (UnitKind::Base(_), UnitKind::Base(_)) => true,
+            (UnitKind::Derived(f1, u1), UnitKind::Derived(f2, u2)) => f1 == f2 && u1 == u2,
+            (
+                UnitKind::Affine {
+                    scale: s1,
+                    offset: o1,
+                    base_unit: u1,
+                },
+                UnitKind::Affine {
+                    scale: s2,
+                    offset: o2,
+                    base_unit: u2,
+                },
+            ) => s1 == s2 && o1 == o2 && u1 == u2,
+            _ => false,
+        }
+    }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+impl Eq for UnitKind {}
+
+impl std::hash::Hash for UnitKind {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        std::mem::discriminant(self).hash(state);
+        match self {
+            UnitKind::Base(_) => {}
+            UnitKind::Derived(factor, unit) => {
+                factor.hash(state);
+                unit.hash(state);
+            }
+            UnitKind::Affine {
+                scale,
+                offset,
+                base_unit,
+            } => {
+                scale.hash(state);
+                offset.hash(state);
+                base_unit.hash(state);
+            }
+        }
+    }
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct CanonicalName {
     pub name: String,
     pub accepts_prefix: AcceptsPrefix,
@@ -36,11 +104,92 @@ impl CanonicalName {
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
-pub struct UnitIdentifier {
-    pub name: String,
-    pub canonical_name: CanonicalName,
+/// The kinds of prefixes that a unit accepts, as declared with the `@metric_prefixes`
+/// and `@binary_prefixes` decorators. This is used for choosing prefixes
+/// automatically (see [`crate::quantity::Quantity::with_auto_prefix`]).
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct PrefixSupport {
+    pub metric: bool,
+    pub binary: bool,
+}
+
+/// A lazily computed value that only depends on the other fields of its containing
+/// struct. It is ignored for comparisons and hashing.
+#[derive(Debug)]
+struct Cached<T>(OnceLock<T>);
+
+impl<T> Default for Cached<T> {
+    fn default() -> Self {
+        Cached(OnceLock::new())
+    }
+}
+
+impl<T> Cached<T> {
+    fn get_or_init(&self, f: impl FnOnce() -> T) -> &T {
+        self.0.get_or_init(f)
+    }
+}
+
+impl<T> PartialEq for Cached<T> {
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
+impl<T> Eq for Cached<T> {}
+
+impl<T> std::hash::Hash for Cached<T> {
+    fn hash<H: std::hash::Hasher>(&self, _state: &mut H) {}
+}
+
+/// Metadata that is ignored for comparisons and hashing.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, Default)]
+struct Ignored<T>(T);
+
+impl<T> PartialEq for Ignored<T> {
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
+impl<T> Eq for Ignored<T> {}
+
+impl<T> std::hash::Hash for Ignored<T> {
+    fn hash<H: std::hash::Hasher>(&self, _state: &mut H) {}
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, PartialEq, Eq, Hash)]
+struct UnitIdentifierInner {
+    name: String,
+    canonical_name: CanonicalName,
     kind: UnitKind,
+    #[cfg_attr(feature = "serde", serde(default))]
+    prefix_support: Ignored<PrefixSupport>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    base_unit_and_factor: Cached<BaseUnitAndFactor>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    sort_key: Cached<Vec<(String, Exponent)>>,
+}
+
+/// Identifies a single (base or derived) unit. Unit identifiers are immutable and
+/// shared, so cloning one is cheap.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Eq)]
+pub struct UnitIdentifier(Arc<UnitIdentifierInner>);
+
+impl PartialEq for UnitIdentifier {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0) || self.0 == other.0
+    }
+}
+
+impl std::hash::Hash for UnitIdentifier {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.0.hash(state);
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -54,29 +203,98 @@ impl std::iter::Product for BaseUnitAndFactor {
 }
 
 impl UnitIdentifier {
+    fn new(name: &str, canonical_name: CanonicalName, kind: UnitKind) -> Self {
+        UnitIdentifier(Arc::new(UnitIdentifierInner {
+            name: name.into(),
+            canonical_name,
+            kind,
+            prefix_support: Ignored::default(),
+            base_unit_and_factor: Cached::default(),
+            sort_key: Cached::default(),
+        }))
+    }
+
+    fn with_prefix_support(&self, prefix_support: PrefixSupport) -> Self {
+        UnitIdentifier(Arc::new(UnitIdentifierInner {
+            name: self.0.name.clone(),
+            canonical_name: self.0.canonical_name.clone(),
+            kind: self.0.kind.clone(),
+            prefix_support: Ignored(prefix_support),
+            base_unit_and_factor: Cached::default(),
+            sort_key: Cached::default(),
+        }))
+    }
+
+    /// The kinds of prefixes that this unit accepts. This is not taken into account
+    /// when comparing units.
+    pub fn prefix_support(&self) -> PrefixSupport {
+        self.0.prefix_support.0
+    }
+
+    pub fn name(&self) -> &str {
+        &self.0.name
+    }
+
+    pub fn canonical_name(&self) -> &CanonicalName {
+        &self.0.canonical_name
+    }
+
+    fn kind(&self) -> &UnitKind {
+        &self.0.kind
+    }
+
     pub fn is_base(&self) -> bool {
-        matches!(self.kind, UnitKind::Base)
+        matches!(self.kind(), UnitKind::Base(_))
+    }
+
+    pub fn is_affine(&self) -> bool {
+        matches!(self.kind(), UnitKind::Affine { .. })
     }
 
     pub fn unit_and_factor(&self) -> BaseUnitAndFactor {
-        match &self.kind {
-            UnitKind::Base => BaseUnitAndFactor(
-                Unit::new_base(&self.name, self.canonical_name.clone()),
-                Number::from_f64(1.0),
+        match self.kind() {
+            UnitKind::Base(_) => BaseUnitAndFactor(
+                Unit::new_base_of_kind(
+                    self.name(),
+                    self.canonical_name().clone(),
+                    self.kind().clone(),
+                ),
+                Number::from_rational(Rational::from_integer(1)),
             ),
-            UnitKind::Derived(factor, defining_unit) => {
-                BaseUnitAndFactor(defining_unit.clone(), *factor)
-            }
+            UnitKind::Derived(factor, defining_unit)
+            | UnitKind::Affine {
+                scale: factor,
+                base_unit: defining_unit,
+                ..
+            } => BaseUnitAndFactor(defining_unit.clone(), *factor),
         }
     }
 
+    /// The base unit representation of this unit, along with the conversion factor.
+    /// The result is cached, as it is needed for every conversion.
     pub fn base_unit_and_factor(&self) -> BaseUnitAndFactor {
-        match &self.kind {
-            UnitKind::Base => BaseUnitAndFactor(
-                Unit::new_base(&self.name, self.canonical_name.clone()),
-                Number::from_f64(1.0),
+        self.0
+            .base_unit_and_factor
+            .get_or_init(|| self.compute_base_unit_and_factor())
+            .clone()
+    }
+
+    fn compute_base_unit_and_factor(&self) -> BaseUnitAndFactor {
+        match self.kind() {
+            UnitKind::Base(_) => BaseUnitAndFactor(
+                Unit::new_base_of_kind(
+                    self.name(),
+                    self.canonical_name().clone(),
+                    self.kind().clone(),
+                ),
+                Number::from_rational(Rational::from_integer(1)),
             ),
-            UnitKind::Derived(factor, defining_unit) => {
+            UnitKind::Derived(factor, defining_unit)
+            | UnitKind::Affine {
+                scale: factor,
+                base_unit: defining_unit,
+                ..
+            } => {
                 let BaseUnitAndFactor(base_unit, defining_unit_factor) = defining_unit
                     .iter()
                     .map(
@@ -91,7 +309,7 @@ impl UnitIdentifier {
                             BaseUnitAndFactor(
                                 base_unit.power(*exponent),
                                 (prefix.factor() * base_unit_factor)
-                                    .pow(&Number::from_f64(exponent.to_f64().unwrap())),
+                                    .pow(&Number::from_rational(*exponent)),
                             )
                         },
                     )
@@ -103,13 +321,26 @@ impl UnitIdentifier {
     }
 
     pub fn sort_key(&self) -> Vec<(String, Exponent)> {
+        self.cached_sort_key().to_vec()
+    }
+
+    /// The sort key is needed for every comparison, so it is only computed once.
+    fn cached_sort_key(&self) -> &[(String, Exponent)] {
+        self.0.sort_key.get_or_init(|| self.compute_sort_key())
+    }
+
+    fn compute_sort_key(&self) -> Vec<(String, Exponent)> {
         use num_integer::Integer;
 
         // TODO: this is more or less a hack. instead of properly sorting by physical
         // dimension, we sort by the name of the corresponding base unit(s).
-        match &self.kind {
-            UnitKind::Base => vec![(self.name.clone(), Exponent::from_integer(1))],
-            UnitKind::Derived(_, defining_unit) => {
+        match self.kind() {
+            UnitKind::Base(_) => vec![(self.name().to_string(), Exponent::from_integer(1))],
+            UnitKind::Derived(_, defining_unit)
+            | UnitKind::Affine {
+                base_unit: defining_unit,
+                ..
+            } => {
                 let base_unit = defining_unit.to_base_unit_representation().0;
                 let mut key: Vec<_> = base_unit
                     .canonicalized()
@@ -151,6 +382,41 @@ impl UnitIdentifier {
             }
         }
     }
+
+    /// See [`Unit::canonical_key`]. This includes everything that is relevant for
+    /// equality, i.e. everything except the name of the physical dimension.
+    fn canonical_key(&self) -> String {
+        let CanonicalName {
+            name: canonical_name,
+            accepts_prefix: AcceptsPrefix { short, long },
+        } = self.canonical_name();
+        let kind = match self.kind() {
+            UnitKind::Base(_) => "base".to_string(),
+            UnitKind::Derived(factor, defining_unit) => format!(
+                "derived({:?}, {})",
+                factor.demote_rational(),
+                defining_unit.canonical_key()
+            ),
+            UnitKind::Affine {
+                scale,
+                offset,
+                base_unit,
+            } => format!(
+                "affine({:?}, {:?}, {})",
+                scale.demote_rational(),
+                offset.demote_rational(),
+                base_unit.canonical_key()
+            ),
+        };
+        format!(
+            "{:?}/{:?}/{}{}/{}",
+            self.name(),
+            canonical_name,
+            u8::from(*short),
+            u8::from(*long),
+            kind
+        )
+    }
 }
 
 impl PartialOrd for UnitIdentifier {
@@ -161,11 +427,12 @@ impl PartialOrd for UnitIdentifier {
 
 impl Ord for UnitIdentifier {
     fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        self.sort_key().cmp(&other.sort_key())
+        self.cached_sort_key().cmp(other.cached_sort_key())
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct UnitFactor {
     pub unit_id: UnitIdentifier,
     pub prefix: Prefix,
@@ -176,7 +443,7 @@ impl Canonicalize for UnitFactor {
     type MergeKey = (Prefix, UnitIdentifier);
 
     fn merge_key(&self) -> Self::MergeKey {
-        (self.prefix, self.unit_id.clone())
+        (self.prefix.clone(), self.unit_id.clone())
     }
 
     fn merge(self, other: Self) -> Self {
@@ -202,24 +469,57 @@ impl Power for UnitFactor {
     }
 }
 
-impl Display for UnitFactor {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let prefix = if self.unit_id.canonical_name.accepts_prefix.short {
+impl UnitFactor {
+    /// The conversion factor of this factor to base units, including the prefix and
+    /// the exponent. For example, the numeric value of `cm²` is `1e-4`.
+    pub fn numeric_value(&self) -> Number {
+        (self.prefix.factor() * self.unit_id.base_unit_and_factor().1)
+            .pow(&Number::from_rational(self.exponent))
+    }
+
+    fn prefixed_name(&self) -> String {
+        let prefix = if self.unit_id.canonical_name().accepts_prefix.short {
             self.prefix.as_string_short()
         } else {
             self.prefix.as_string_long()
         };
 
+        format!("{}{}", prefix, self.unit_id.canonical_name().name)
+    }
+}
+
+impl Display for UnitFactor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
             f,
-            "{}{}{}",
-            prefix,
-            self.unit_id.canonical_name.name,
+            "{}{}",
+            self.prefixed_name(),
             pretty_exponent(&self.exponent)
         )
     }
 }
 
+/// Options for [`Unit::to_string_with`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UnitFormatOptions {
+    /// The separator between multiplied factors, e.g. `·`, ` ` or `*`.
+    pub times_separator: char,
+    /// Whether to use a solidus (`m/s`) or negative exponents only (`m·s⁻¹`).
+    pub use_solidus: bool,
+    /// Whether to use ASCII exponents (`s^-1`) instead of Unicode superscripts (`s⁻¹`).
+    pub ascii_exponents: bool,
+}
+
+impl Default for UnitFormatOptions {
+    fn default() -> Self {
+        Self {
+            times_separator: '·',
+            use_solidus: true,
+            ascii_exponents: false,
+        }
+    }
+}
+
 pub type Unit = Product<UnitFactor, false>;
 
 impl Unit {
@@ -227,22 +527,71 @@ impl Unit {
         Self::unity()
     }
 
+    /// The factors of this unit, in the order in which they are stored (and displayed).
+    pub fn factors(&self) -> impl Iterator<Item = &UnitFactor> {
+        self.iter()
+    }
+
+    /// The number of factors of this unit. This is zero for [`Unit::scalar`].
+    pub fn num_factors(&self) -> usize {
+        self.iter().count()
+    }
+
     pub fn is_scalar(&self) -> bool {
         self == &Self::scalar()
     }
 
+    /// A string that uniquely identifies this unit, suitable as a key in maps or
+    /// caches. Units that compare equal have the same key, and different units are
+    /// guaranteed to have different keys.
+    pub fn canonical_key(&self) -> String {
+        let factors = self.canonicalized();
+        let keys = factors.iter().map(|f| {
+            format!(
+                "[{}; {:?}; {}]",
+                f.unit_id.canonical_key(),
+                f.prefix,
+                f.exponent
+            )
+        });
+        format!("({})", keys.format(", "))
+    }
+
+    /// Returns a [`UnitBuilder`] for constructing compound units factor by factor.
+    pub fn builder() -> UnitBuilder {
+        UnitBuilder::default()
+    }
+
     pub fn new_base(name: &str, canonical_name: CanonicalName) -> Self {
+        Self::new_base_of_kind(name, canonical_name, UnitKind::Base(None))
+    }
+
+    /// Like [`Unit::new_base`], but also records the name of the physical dimension
+    /// of the base unit (like `Length`).
+    pub fn new_base_with_dimension(
+        name: &str,
+        canonical_name: CanonicalName,
+        dimension: &str,
+    ) -> Self {
+        Self::new_base_of_kind(name, canonical_name, UnitKind::Base(Some(dimension.into())))
+    }
+
+    fn new_base_of_kind(name: &str, canonical_name: CanonicalName, kind: UnitKind) -> Self {
         Unit::from_factor(UnitFactor {
             prefix: Prefix::none(),
-            unit_id: UnitIdentifier {
-                name: name.into(),
-                canonical_name,
-                kind: UnitKind::Base,
-            },
+            unit_id: UnitIdentifier::new(name, canonical_name, kind),
             exponent: Rational::from_integer(1),
         })
     }
 
+    /// Records the kinds of prefixes that the unit factors accept, see [`PrefixSupport`].
+    pub fn with_prefix_support(self, prefix_support: PrefixSupport) -> Self {
+        Unit::from_factors(self.into_iter().map(|f| UnitFactor {
+            unit_id: f.unit_id.with_prefix_support(prefix_support),
+            ..f
+        }))
+    }
+
     pub fn new_derived(
         name: &str,
         canonical_name: CanonicalName,
@@ -251,11 +600,33 @@ impl Unit {
     ) -> Self {
         Unit::from_factor(UnitFactor {
             prefix: Prefix::none(),
-            unit_id: UnitIdentifier {
-                name: name.into(),
+            unit_id: UnitIdentifier::new(
+                name,
                 canonical_name,
-                kind: UnitKind::Derived(factor, base_unit),
-            },
+                UnitKind::Derived(factor, base_unit),
+            ),
+            exponent: Rational::from_integer(1),
+        })
+    }
+
+    pub fn new_affine(
+        name: &str,
+        canonical_name: CanonicalName,
+        scale: ConversionFactor,
+        offset: Number,
+        base_unit: Unit,
+    ) -> Self {
+        Unit::from_factor(UnitFactor {
+            prefix: Prefix::none(),
+            unit_id: UnitIdentifier::new(
+                name,
+                canonical_name,
+                UnitKind::Affine {
+                    scale,
+                    offset,
+                    base_unit,
+                },
+            ),
             exponent: Rational::from_integer(1),
         })
     }
@@ -268,6 +639,29 @@ impl Unit {
         Self::from_factors(factors)
     }
 
+    /// Returns this unit with all prefixes removed (`km/ms` becomes `m/s`). Note
+    /// that this is a purely structural transformation, the prefix factors are dropped.
+    pub fn without_prefixes(&self) -> Self {
+        self.iter()
+            .map(|f| UnitFactor {
+                prefix: Prefix::none(),
+                ..f.clone()
+            })
+            .product::<Self>()
+            .canonicalized()
+    }
+
+    /// Like [`Unit::without_prefixes`], but also returns the numeric factor of the
+    /// removed prefixes. For example, `m·ms·s` becomes `m·s²` with a factor of `10⁻³`,
+    /// so a quantity `x m·ms·s` is equal to `(x · 10⁻³) m·s²`.
+    pub fn absorb_prefixes(&self) -> (Self, Number) {
+        let factor = self
+            .iter()
+            .map(|f| f.prefix.factor().pow(&Number::from_rational(f.exponent)))
+            .product();
+        (self.without_prefixes(), factor)
+    }
+
     pub fn to_base_unit_representation(&self) -> (Self, ConversionFactor) {
         // TODO: reduce wrapping/unwrapping and duplication.
 
@@ -283,23 +677,131 @@ impl Unit {
             .product::<Self>()
             .canonicalized();
 
-        let factor = self
-            .iter()
-            .map(
-                |UnitFactor {
-                     prefix,
-                     unit_id: base_unit,
-                     exponent,
-                 }| {
-                    (prefix.factor() * base_unit.base_unit_and_factor().1)
-                        .pow(&Number::from_f64(exponent.to_f64().unwrap())) // TODO do we want to use exponent.to_f64?
-                },
-            )
-            .product();
+        let factor = self.iter().map(UnitFactor::numeric_value).product();
 
         (base_unit_representation, factor)
     }
 
+    /// Returns the unit factors that both units have in common, i.e. the part that
+    /// cancels out in a conversion between them. Only factors with the same prefix
+    /// and the same sign of the exponent are taken into account. For example, the
+    /// common factors of `km/h` and `mile/h` are `h⁻¹`.
+    pub fn common_factors(&self, other: &Unit) -> Unit {
+        let mut common_factors = Unit::scalar();
+        let other_canonicalized = other.canonicalized();
+
+        for factor in self.canonicalized().iter() {
+            if let Some(other_factor) = other_canonicalized
+                .iter()
+                .find(|&f| factor.prefix == f.prefix && factor.unit_id == f.unit_id)
+            {
+                if factor.exponent > Rational::zero() && other_factor.exponent > Rational::zero() {
+                    common_factors = common_factors
+                        * Unit::from_factor(UnitFactor {
+                            exponent: std::cmp::min(factor.exponent, other_factor.exponent),
+                            ..factor.clone()
+                        });
+                } else if factor.exponent < Rational::zero()
+                    && other_factor.exponent < Rational::zero()
+                {
+                    common_factors = common_factors
+                        * Unit::from_factor(UnitFactor {
+                            exponent: std::cmp::max(factor.exponent, other_factor.exponent),
+                            ..factor.clone()
+                        });
+                }
+            }
+        }
+
+        common_factors
+    }
+
+    /// Returns both units with their common factors (see [`Unit::common_factors`])
+    /// removed. For `km/h` and `mile/h`, this returns `(km, mile)`.
+    pub fn strip_common_with(&self, other: &Unit) -> (Unit, Unit) {
+        let common_factors = self.common_factors(other);
+        (
+            (self.clone() / common_factors.clone()).canonicalized(),
+            (other.clone() / common_factors).canonicalized(),
+        )
+    }
+
+    /// Splits this unit into the factors with positive exponents and the (inverted)
+    /// factors with negative exponents. For `kg/(m·s²)`, this returns `(kg, m·s²)`.
+    pub fn numerator_denominator(&self) -> (Unit, Unit) {
+        let (numerator, denominator): (Vec<_>, Vec<_>) = self
+            .iter()
+            .cloned()
+            .partition(|f| f.exponent > Exponent::zero());
+        (
+            Unit::from_factors(numerator),
+            Unit::from_factors(
+                denominator
+                    .into_iter()
+                    .map(|f| f.power(Exponent::from_integer(-1))),
+            ),
+        )
+    }
+
+    /// Returns the canonicalized inverse of this unit, i.e. `1 / self`.
+    pub fn inverse(self) -> Self {
+        self.powi(-1).canonicalized()
+    }
+
+    /// Raises this unit to the power of `1/n`.
+    pub fn nth_root(self, n: i32) -> Result<Self, QuantityError> {
+        if n == 0 {
+            return Err(QuantityError::NonRationalExponent);
+        }
+        Ok(self.power(Rational::new(1, n.into())))
+    }
+
+    /// Returns true if both units have the same base unit representation, i.e.
+    /// if they can be converted into each other.
+    pub fn is_compatible_with(&self, other: &Unit) -> bool {
+        self.to_base_unit_representation().0 == other.to_base_unit_representation().0
+    }
+
+    /// Returns the physical dimension of this unit. Base units without a known physical
+    /// dimension are treated as their own dimension, named after the unit. Dimensionless
+    /// units (including angles like `rad`, which are defined as `m/m`) return
+    /// [`Dimension::scalar`].
+    pub fn dimension(&self) -> Dimension {
+        Dimension::from_factors(self.to_base_unit_representation().0.iter().map(|f| {
+            let name = match f.unit_id.kind() {
+                UnitKind::Base(Some(dimension)) => dimension.clone(),
+                _ => f.unit_id.name().to_string(),
+            };
+            (BaseDimension(name), f.exponent)
+        }))
+    }
+
+    /// Whether any factor of this unit is an affine unit like `°C`. This is cheap
+    /// to check, in contrast to [`Unit::affine_offset`].
+    pub fn has_affine_factor(&self) -> bool {
+        self.iter().any(|f| f.unit_id.is_affine())
+    }
+
+    /// If this unit consists of a single affine unit factor (like `°C`), return the
+    /// offset (in base units) that needs to be added after applying the conversion
+    /// factor from [`Unit::to_base_unit_representation`].
+    pub fn affine_offset(&self) -> Option<Number> {
+        if !self.has_affine_factor() {
+            return None;
+        }
+
+        let canonicalized = self.canonicalized();
+        let factor = canonicalized.iter().exactly_one().ok()?;
+        match factor.unit_id.kind() {
+            UnitKind::Affine {
+                offset, base_unit, ..
+            } if factor.exponent == Rational::from_integer(1) => {
+                Some(*offset * base_unit.to_base_unit_representation().1)
+            }
+            _ => None,
+        }
+    }
+
     #[cfg(test)]
     pub fn meter() -> Self {
         Self::new_base(
@@ -362,16 +864,38 @@ impl Unit {
     }
 
     #[cfg(test)]
+    pub fn celsius() -> Self {
+        Self::new_affine(
+            "degree_celsius",
+            CanonicalName::new("°C", AcceptsPrefix::none()),
+            Number::from_f64(1.0),
+            Number::from_f64(273.15),
+            Self::kelvin(),
+        )
+    }
+
+    #[cfg(test)]
+    pub fn fahrenheit() -> Self {
+        Self::new_affine(
+            "degree_fahrenheit",
+            CanonicalName::new("°F", AcceptsPrefix::none()),
+            Number::from_f64(5.0 / 9.0),
+            Number::from_f64(459.67 * 5.0 / 9.0),
+            Self::kelvin(),
+        )
+    }
+
+    /// The radian, as a dimensionless unit. This is used for the results of inverse
+    /// trigonometric functions.
     pub fn radian() -> Self {
         Self::new_derived(
             "radian",
             CanonicalName::new("rad", AcceptsPrefix::only_long()),
             Number::from_f64(1.0),
-            Self::meter() / Self::meter(),
+            Self::scalar(),
         )
     }
 
-    #[cfg(test)]
     pub fn degree() -> Self {
         Self::new_derived(
             "degree",
@@ -381,12 +905,72 @@ impl Unit {
         )
     }
 
-    #[cfg(test)]
+    /// The gradian, a right angle is 100 gradians (400 per turn).
+    pub fn gradian() -> Self {
+        Self::new_derived(
+            "gradian",
+            CanonicalName::new("grad", AcceptsPrefix::none()),
+            Number::from_rational(Rational::new(9, 10)),
+            Self::degree(),
+        )
+    }
+
+    /// A full turn, i.e. `2π rad`.
+    pub fn turn() -> Self {
+        Self::new_derived(
+            "turn",
+            CanonicalName::new("turn", AcceptsPrefix::none()),
+            Number::from_f64(std::f64::consts::TAU),
+            Self::radian(),
+        )
+    }
+
+    /// A revolution, i.e. `360°`. This is equivalent to [`Unit::turn`].
+    pub fn revolution() -> Self {
+        Self::new_derived(
+            "revolution",
+            CanonicalName::new("rev", AcceptsPrefix::none()),
+            Number::from_rational(Rational::from_integer(360)),
+            Self::degree(),
+        )
+    }
+
+    /// The percent (`%`), a dimensionless unit with a factor of `1/100`.
     pub fn percent() -> Self {
         Self::new_derived(
             "percent",
             CanonicalName::new("%", AcceptsPrefix::none()),
-            Number::from_f64(1e-2),
+            Number::from_rational(Rational::new(1, 100)),
+            Self::scalar(),
+        )
+    }
+
+    /// The permille (`‰`), a dimensionless unit with a factor of `1/1000`.
+    pub fn permille() -> Self {
+        Self::new_derived(
+            "permille",
+            CanonicalName::new("‰", AcceptsPrefix::none()),
+            Number::from_rational(Rational::new(1, 1000)),
+            Self::scalar(),
+        )
+    }
+
+    /// Parts per million (`ppm`), a dimensionless unit with a factor of `10⁻⁶`.
+    pub fn ppm() -> Self {
+        Self::new_derived(
+            "partspermillion",
+            CanonicalName::new("ppm", AcceptsPrefix::none()),
+            Number::from_rational(Rational::new(1, 1_000_000)),
+            Self::scalar(),
+        )
+    }
+
+    /// Parts per billion (`ppb`), a dimensionless unit with a factor of `10⁻⁹`.
+    pub fn ppb() -> Self {
+        Self::new_derived(
+            "partsperbillion",
+            CanonicalName::new("ppb", AcceptsPrefix::none()),
+            Number::from_rational(Rational::new(1, 1_000_000_000)),
             Self::scalar(),
         )
     }
@@ -411,6 +995,26 @@ impl Unit {
         )
     }
 
+    #[cfg(test)]
+    pub fn joule() -> Self {
+        Self::new_derived(
+            "joule",
+            CanonicalName::new("J", AcceptsPrefix::only_short()),
+            Number::from_f64(1.0),
+            Unit::newton() * Unit::meter(),
+        )
+    }
+
+    #[cfg(test)]
+    pub fn watt() -> Self {
+        Self::new_derived(
+            "watt",
+            CanonicalName::new("W", AcceptsPrefix::only_short()),
+            Number::from_f64(1.0),
+            Unit::joule() / Unit::second(),
+        )
+    }
+
     #[cfg(test)]
     pub fn minute() -> Self {
         Self::new_derived(
@@ -507,6 +1111,124 @@ impl Unit {
     }
 }
 
+impl Unit {
+    pub fn to_string_with(&self, options: UnitFormatOptions) -> String {
+        let format_factors = |factors: &[&UnitFactor], invert: bool| -> String {
+            factors
+                .iter()
+                .map(|f| {
+                    let exponent = if invert { -f.exponent } else { f.exponent };
+                    let exponent = if options.ascii_exponents {
+                        ascii_exponent(&exponent)
+                    } else {
+                        pretty_exponent(&exponent)
+                    };
+                    format!("{}{}", f.prefixed_name(), exponent)
+                })
+                .join(&options.times_separator.to_string())
+        };
+
+        let factors: Vec<_> = self.iter().collect();
+        if !options.use_solidus {
+            return format_factors(&factors, false);
+        }
+
+        let (positive, negative): (Vec<_>, Vec<_>) = factors
+            .into_iter()
+            .partition(|f| f.exponent > Exponent::zero());
+
+        match (&positive[..], &negative[..]) {
+            (_, []) => format_factors(&positive, false),
+            ([], _) => format_factors(&negative, false),
+            (_, [_]) => format!(
+                "{}/{}",
+                format_factors(&positive, false),
+                format_factors(&negative, true)
+            ),
+            (_, _) => format!(
+                "{}/({})",
+                format_factors(&positive, false),
+                format_factors(&negative, true)
+            ),
+        }
+    }
+}
+
+/// A builder for compound units like `kg·m/s²`. Every call to [`UnitBuilder::base`]
+/// or [`UnitBuilder::derived`] adds a new factor, [`UnitBuilder::prefix`] and
+/// [`UnitBuilder::power`] modify the factor that was added last.
+///
+/// # Panics
+///
+/// Calling [`UnitBuilder::prefix`] or [`UnitBuilder::power`] before any factor has
+/// been added panics.
+#[derive(Debug, Clone, Default)]
+pub struct UnitBuilder {
+    factors: Vec<UnitFactor>,
+}
+
+impl UnitBuilder {
+    /// Adds a base unit with the given name and symbol. Like the built-in base
+    /// units, the symbol accepts short prefixes.
+    pub fn base(self, name: &str, symbol: &str) -> Self {
+        self.unit(Unit::new_base(
+            name,
+            CanonicalName::new(symbol, AcceptsPrefix::only_short()),
+        ))
+    }
+
+    /// Adds a unit that is defined as `factor` times `defining_unit`.
+    pub fn derived(
+        self,
+        name: &str,
+        symbol: &str,
+        factor: ConversionFactor,
+        defining_unit: Unit,
+    ) -> Self {
+        self.unit(Unit::new_derived(
+            name,
+            CanonicalName::new(symbol, AcceptsPrefix::only_short()),
+            factor,
+            defining_unit,
+        ))
+    }
+
+    fn unit(mut self, unit: Unit) -> Self {
+        self.factors.extend(unit);
+        self
+    }
+
+    /// Sets the prefix of the last factor.
+    pub fn prefix(mut self, prefix: Prefix) -> Self {
+        self.last_factor().prefix = prefix;
+        self
+    }
+
+    /// Sets the exponent of the last factor.
+    pub fn power(mut self, exponent: i32) -> Self {
+        self.last_factor().exponent = Exponent::from_integer(exponent.into());
+        self
+    }
+
+    fn last_factor(&mut self) -> &mut UnitFactor {
+        self.factors
+            .last_mut()
+            .expect("a base or derived unit needs to be added first")
+    }
+
+    pub fn build(self) -> Unit {
+        Unit::from_factors(self.factors).canonicalized()
+    }
+}
+
+impl Unit {
+    /// Like the [`Display`] output, but the factors are sorted by the given key first.
+    /// Factors with the same key keep their original order.
+    pub fn to_string_ordered(&self, key: impl Fn(&UnitFactor) -> i64) -> String {
+        Unit::from_factors(self.iter().cloned().sorted_by_key(|f| key(f))).to_string()
+    }
+}
+
 impl Display for Unit {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.write_str(&self.as_string(|f| f.exponent, '·', '/', false))
@@ -576,20 +1298,20 @@ mod tests {
         let meter_per_second = Unit::from_factors([
             UnitFactor {
                 prefix: Prefix::none(),
-                unit_id: UnitIdentifier {
-                    name: "meter".into(),
-                    canonical_name: CanonicalName::new("m", AcceptsPrefix::only_short()),
-                    kind: UnitKind::Base,
-                },
+                unit_id: UnitIdentifier::new(
+                    "meter",
+                    CanonicalName::new("m", AcceptsPrefix::only_short()),
+                    UnitKind::Base(None),
+                ),
                 exponent: Rational::from_integer(1),
             },
             UnitFactor {
                 prefix: Prefix::none(),
-                unit_id: UnitIdentifier {
-                    name: "second".into(),
-                    canonical_name: CanonicalName::new("s", AcceptsPrefix::only_short()),
-                    kind: UnitKind::Base,
-                },
+                unit_id: UnitIdentifier::new(
+                    "second",
+                    CanonicalName::new("s", AcceptsPrefix::only_short()),
+                    UnitKind::Base(None),
+                ),
                 exponent: Rational::from_integer(-1),
             },
         ]);
@@ -648,16 +1370,83 @@ mod tests {
             millimeter,
             Unit::from_factors([UnitFactor {
                 prefix: Prefix::Metric(-3),
-                unit_id: UnitIdentifier {
-                    name: "meter".into(),
-                    canonical_name: CanonicalName::new("m", AcceptsPrefix::only_short()),
-                    kind: UnitKind::Base,
-                },
+                unit_id: UnitIdentifier::new(
+                    "meter",
+                    CanonicalName::new("m", AcceptsPrefix::only_short()),
+                    UnitKind::Base(None)
+                ),
                 exponent: Rational::from_integer(1),
             }])
         );
     }
 
+    #[test]
+    fn si_2022_prefixes() {
+        let cases = [
+            (Prefix::quetta(), "Qm", 1e30),
+            (Prefix::ronna(), "Rm", 1e27),
+            (Prefix::ronto(), "rm", 1e-27),
+            (Prefix::quecto(), "qm", 1e-30),
+        ];
+        for (prefix, display, factor) in cases {
+            assert_eq!(
+                Unit::meter().with_prefix(prefix.clone()).to_string(),
+                display
+            );
+            assert_relative_eq!(prefix.factor().to_f64(), factor, max_relative = 1e-15);
+        }
+        assert_eq!(Prefix::quetta().as_string_long(), "quetta");
+        assert_eq!(Prefix::quecto().as_string_long(), "quecto");
+    }
+
+    #[test]
+    fn custom_prefix() {
+        let dozen = || Prefix::Custom {
+            short: "dz".into(),
+            long: "dozen".into(),
+            factor: Number::from_f64(12.0),
+        };
+
+        let dozen_meter = Unit::meter().with_prefix(dozen());
+        assert_eq!(dozen_meter.to_string(), "dzm");
+        let (base, factor) = dozen_meter.to_base_unit_representation();
+        assert_eq!(base, Unit::meter());
+        assert_relative_eq!(factor.to_f64(), 12.0);
+
+        let egg = Unit::new_base("egg", CanonicalName::new("egg", AcceptsPrefix::only_long()));
+        assert_eq!(egg.with_prefix(dozen()).to_string(), "dozenegg");
+
+        assert_eq!(
+            Unit::meter().with_prefix(dozen()),
+            Unit::meter().with_prefix(dozen())
+        );
+        assert_ne!(
+            Unit::meter().with_prefix(dozen()),
+            Unit::meter().with_prefix(Prefix::Custom {
+                short: "dz".into(),
+                long: "dozen".into(),
+                factor: Number::from_f64(13.0),
+            })
+        );
+    }
+
+    #[test]
+    fn without_prefixes() {
+        assert_eq!(Unit::kilometer().without_prefixes(), Unit::meter());
+        assert_eq!(Unit::millimeter().without_prefixes(), Unit::meter());
+        assert_eq!(
+            Unit::kilometer().without_prefixes(),
+            Unit::millimeter().without_prefixes()
+        );
+        assert_ne!(Unit::kilometer(), Unit::millimeter());
+
+        assert_eq!(
+            (Unit::meter() * Unit::second().with_prefix(Prefix::milli()) * Unit::second())
+                .without_prefixes(),
+            Unit::meter() * Unit::second().powi(2)
+        );
+    }
+
     #[test]
     fn to_base_unit_representation_basic() {
         let hour = Unit::hour();
@@ -687,6 +1476,355 @@ mod tests {
         );
     }
 
+    #[test]
+    fn base_unit_and_factor_is_cached() {
+        let mile = Unit::mile();
+        let unit_id = &mile.iter().next().unwrap().unit_id;
+        assert!(unit_id.0.base_unit_and_factor.0.get().is_none());
+
+        let BaseUnitAndFactor(base_unit, factor) = unit_id.base_unit_and_factor();
+        assert!(unit_id.0.base_unit_and_factor.0.get().is_some());
+        assert_eq!(base_unit, Unit::meter());
+        assert_relative_eq!(factor.to_f64(), 1609.344, epsilon = 1e-9);
+
+        let BaseUnitAndFactor(cached_base_unit, cached_factor) = unit_id.base_unit_and_factor();
+        assert_eq!(cached_base_unit, base_unit);
+        assert_eq!(cached_factor, factor);
+
+        // The cache does not affect comparisons
+        assert_eq!(mile, Unit::mile());
+    }
+
+    #[test]
+    fn unit_identifier_clones_are_shared() {
+        let mile = Unit::mile();
+        let unit_id = &mile.iter().next().unwrap().unit_id;
+        let clone = unit_id.clone();
+        assert!(Arc::ptr_eq(&unit_id.0, &clone.0));
+
+        let fresh = Unit::mile();
+        let fresh_id = &fresh.iter().next().unwrap().unit_id;
+        assert!(!Arc::ptr_eq(&unit_id.0, &fresh_id.0));
+        assert_eq!(&clone, fresh_id);
+        assert_eq!(clone.name(), "mile");
+        assert_eq!(clone.canonical_name().name, "mi");
+        assert!(!clone.is_base());
+        assert_eq!(clone.sort_key(), fresh_id.sort_key());
+        assert_eq!(
+            clone.base_unit_and_factor().0,
+            fresh_id.base_unit_and_factor().0
+        );
+    }
+
+    #[test]
+    fn sort_key_is_cached() {
+        let units = [
+            Unit::meter(),
+            Unit::kilometer(),
+            Unit::hertz(),
+            Unit::newton(),
+            Unit::joule(),
+            Unit::watt(),
+            Unit::mile(),
+            Unit::kph(),
+            Unit::byte(),
+        ];
+        for unit in &units {
+            let unit_id = &unit.iter().next().unwrap().unit_id;
+            let uncached = unit_id.compute_sort_key();
+            assert_eq!(unit_id.sort_key(), uncached);
+            assert!(unit_id.0.sort_key.0.get().is_some());
+            assert_eq!(unit_id.sort_key(), uncached);
+        }
+
+        let mut factors: Vec<_> = units
+            .iter()
+            .cycle()
+            .take(1000)
+            .flat_map(|u| u.iter().cloned())
+            .collect();
+        factors.sort_by(|a, b| a.unit_id.cmp(&b.unit_id));
+        assert!(factors
+            .windows(2)
+            .all(|w| w[0].unit_id.compute_sort_key() <= w[1].unit_id.compute_sort_key()));
+    }
+
+    #[test]
+    fn to_string_ordered() {
+        let mass_first = |f: &UnitFactor| if f.unit_id.name() == "gram" { 0 } else { 1 };
+
+        let unit = Unit::meter() * Unit::kilogram();
+        assert_eq!(unit.to_string(), "m·kg");
+        assert_eq!(unit.to_string_ordered(mass_first), "kg·m");
+
+        let unit = Unit::meter() * Unit::second().powi(-2) * Unit::kilogram();
+        assert_eq!(unit.to_string_ordered(mass_first), "kg·m/s²");
+        assert_eq!(unit.to_string_ordered(|_| 0), unit.to_string());
+    }
+
+    #[test]
+    fn builder() {
+        let unit = Unit::builder()
+            .base("gram", "g")
+            .prefix(Prefix::kilo())
+            .base("meter", "m")
+            .base("second", "s")
+            .power(-2)
+            .build();
+        assert_eq!(
+            unit,
+            Unit::kilogram() * Unit::meter() / Unit::second().powi(2)
+        );
+        assert_eq!(unit.to_string(), "kg·m/s²");
+
+        let unit = Unit::builder()
+            .derived(
+                "newton",
+                "N",
+                Number::from_f64(1.0),
+                Unit::kilogram() * Unit::meter() / Unit::second().powi(2),
+            )
+            .prefix(Prefix::kilo())
+            .base("meter", "m")
+            .build();
+        assert_eq!(
+            unit,
+            Unit::newton().with_prefix(Prefix::kilo()) * Unit::meter()
+        );
+
+        assert_eq!(Unit::builder().build(), Unit::scalar());
+    }
+
+    #[test]
+    fn canonical_key() {
+        assert_ne!(
+            Unit::kilometer().canonical_key(),
+            Unit::millimeter().canonical_key()
+        );
+        assert_ne!(
+            Unit::meter().canonical_key(),
+            Unit::kilometer().canonical_key()
+        );
+        assert_ne!(
+            Unit::hertz().canonical_key(),
+            Unit::second().powi(-1).canonical_key()
+        );
+
+        let a = Unit::meter() / Unit::second();
+        let b = Unit::second().powi(-1) * Unit::meter() * Unit::second() / Unit::second();
+        assert_eq!(a, b);
+        assert_eq!(a.canonical_key(), b.canonical_key());
+
+        assert_eq!(
+            Unit::scalar().canonical_key(),
+            (Unit::meter() / Unit::meter()).canonical_key()
+        );
+    }
+
+    #[test]
+    fn absorb_prefixes() {
+        let unit = Unit::meter() * Unit::second().with_prefix(Prefix::milli()) * Unit::second();
+        let (unit, factor) = unit.absorb_prefixes();
+        assert_eq!(unit, Unit::meter() * Unit::second().powi(2));
+        assert_eq!(unit.to_string(), "m·s²");
+        assert_relative_eq!(factor.to_f64(), 1e-3, epsilon = 1e-16);
+
+        let (unit, factor) = (Unit::kilometer().powi(2)
+            / Unit::second().with_prefix(Prefix::milli()))
+        .absorb_prefixes();
+        assert_eq!(unit, Unit::meter().powi(2) / Unit::second());
+        assert_relative_eq!(factor.to_f64(), 1e9, epsilon = 1e-6);
+
+        let (unit, factor) = Unit::newton().absorb_prefixes();
+        assert_eq!(unit, Unit::newton());
+        assert_eq!(factor.to_f64(), 1.0);
+    }
+
+    #[test]
+    fn numeric_value() {
+        let kilometer = Unit::kilometer();
+        let factor = kilometer.iter().next().unwrap();
+        assert_eq!(factor.numeric_value().to_f64(), 1000.0);
+
+        let square_centimeter = Unit::centimeter().powi(2);
+        let factor = square_centimeter.iter().next().unwrap();
+        assert_relative_eq!(factor.numeric_value().to_f64(), 1e-4, epsilon = 1e-16);
+
+        let per_hour = Unit::hour().powi(-1);
+        let factor = per_hour.iter().next().unwrap();
+        assert_relative_eq!(factor.numeric_value().to_f64(), 1.0 / 3600.0);
+    }
+
+    #[test]
+    fn factors() {
+        let unit = Unit::kilogram() * Unit::meter() / Unit::second().powi(2);
+        assert_eq!(unit.num_factors(), 3);
+        assert_eq!(Unit::scalar().num_factors(), 0);
+
+        let negative: Vec<_> = unit
+            .factors()
+            .filter(|f| f.exponent < Exponent::zero())
+            .collect();
+        assert_eq!(negative.len(), 1);
+        assert_eq!(negative[0].unit_id.name(), "second");
+        assert_eq!(negative[0].exponent, Exponent::from_integer(-2));
+    }
+
+    #[test]
+    fn strip_common_with() {
+        assert_eq!(
+            (Unit::kilometer() / Unit::hour()).strip_common_with(&(Unit::mile() / Unit::hour())),
+            (Unit::kilometer(), Unit::mile())
+        );
+        assert_eq!(
+            (Unit::meter().powi(3) / Unit::second())
+                .strip_common_with(&(Unit::meter().powi(2) / Unit::hour())),
+            (Unit::meter() / Unit::second(), Unit::hour().powi(-1))
+        );
+        assert_eq!(
+            Unit::meter().strip_common_with(&Unit::second()),
+            (Unit::meter(), Unit::second())
+        );
+    }
+
+    #[test]
+    fn numerator_denominator() {
+        let unit = Unit::kilogram() / (Unit::meter() * Unit::second().powi(2));
+        assert_eq!(
+            unit.numerator_denominator(),
+            (Unit::kilogram(), Unit::meter() * Unit::second().powi(2))
+        );
+
+        let unit = Unit::kilogram() * Unit::meter();
+        assert_eq!(unit.numerator_denominator(), (unit.clone(), Unit::scalar()));
+
+        let unit = Unit::hertz().powi(-1) / Unit::meter();
+        assert_eq!(
+            unit.numerator_denominator(),
+            (Unit::scalar(), Unit::hertz() * Unit::meter())
+        );
+    }
+
+    #[test]
+    fn common_factors() {
+        assert_eq!(
+            (Unit::kilometer() / Unit::hour()).common_factors(&(Unit::mile() / Unit::hour())),
+            Unit::hour().powi(-1)
+        );
+        assert_eq!(
+            (Unit::meter().powi(3) / Unit::second())
+                .common_factors(&(Unit::meter().powi(2) * Unit::second())),
+            Unit::meter().powi(2)
+        );
+
+        assert_eq!(
+            (Unit::meter() / Unit::second()).common_factors(&(Unit::second() / Unit::meter())),
+            Unit::scalar()
+        );
+        assert_eq!(
+            Unit::kilometer().common_factors(&Unit::meter()),
+            Unit::scalar()
+        );
+        assert_eq!(Unit::meter().common_factors(&Unit::hour()), Unit::scalar());
+    }
+
+    #[test]
+    fn inverse() {
+        assert_eq!(Unit::second().inverse(), Unit::second().powi(-1));
+
+        let unit = Unit::kilogram() * Unit::meter() / Unit::second().powi(2);
+        assert_eq!(
+            unit.clone()
+                .inverse()
+                .inverse()
+                .into_iter()
+                .collect::<Vec<_>>(),
+            unit.canonicalized().into_iter().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn nth_root() {
+        assert_eq!(Unit::meter().powi(2).nth_root(2), Ok(Unit::meter()));
+        assert_eq!(Unit::meter().powi(6).nth_root(3), Ok(Unit::meter().powi(2)));
+        assert_eq!(
+            Unit::second().powi(2).nth_root(-2),
+            Ok(Unit::second().powi(-1))
+        );
+        assert_eq!(
+            Unit::meter().nth_root(0),
+            Err(QuantityError::NonRationalExponent)
+        );
+    }
+
+    #[test]
+    fn is_compatible_with() {
+        assert!((Unit::kilometer() / Unit::hour())
+            .is_compatible_with(&(Unit::meter() / Unit::second())));
+        assert!(Unit::hertz().is_compatible_with(&Unit::second().powi(-1)));
+        assert!(Unit::radian().is_compatible_with(&Unit::scalar()));
+
+        assert!(!Unit::meter().is_compatible_with(&Unit::second()));
+        assert!(!Unit::meter().is_compatible_with(&Unit::meter().powi(2)));
+    }
+
+    #[test]
+    fn dimension() {
+        let base =
+            |name: &str, exponent| (BaseDimension(name.into()), Exponent::from_integer(exponent));
+
+        assert_eq!(
+            Unit::newton().dimension(),
+            Dimension::from_factors([base("gram", 1), base("meter", 1), base("second", -2)])
+        );
+        assert_eq!(
+            Unit::hertz().dimension(),
+            Dimension::from_factors([base("second", -1)])
+        );
+
+        assert!((Unit::meter() / Unit::meter()).dimension().is_scalar());
+        assert!(Unit::radian().dimension().is_scalar());
+        assert!(Unit::scalar().dimension().is_scalar());
+    }
+
+    #[test]
+    fn affine_offset() {
+        assert_eq!(Unit::kelvin().affine_offset(), None);
+        assert_eq!(
+            Unit::celsius().affine_offset(),
+            Some(Number::from_f64(273.15))
+        );
+        assert_eq!((Unit::celsius() / Unit::meter()).affine_offset(), None);
+        assert_eq!(Unit::celsius().powi(2).affine_offset(), None);
+
+        let (base_unit_representation, conversion_factor) =
+            Unit::fahrenheit().to_base_unit_representation();
+        assert_eq!(base_unit_representation, Unit::kelvin());
+        assert_relative_eq!(conversion_factor.to_f64(), 5.0 / 9.0, epsilon = 1e-12);
+    }
+
+    #[test]
+    fn hash() {
+        use std::collections::hash_map::DefaultHasher;
+        use std::hash::{Hash, Hasher};
+
+        let hash = |unit: &Unit| {
+            let mut hasher = DefaultHasher::new();
+            unit.hash(&mut hasher);
+            hasher.finish()
+        };
+
+        assert_eq!(
+            hash(&(Unit::meter() * Unit::second())),
+            hash(&(Unit::second() * Unit::meter()))
+        );
+        assert_eq!(
+            hash(&(Unit::meter() * Unit::second() / Unit::second())),
+            hash(&Unit::meter())
+        );
+        assert_ne!(hash(&Unit::kilometer()), hash(&Unit::meter()));
+    }
+
     #[test]
     fn to_string() {
         assert_eq!(Unit::meter().to_string(), "m");
@@ -767,6 +1905,40 @@ mod tests {
         );
     }
 
+    #[test]
+    fn to_string_with() {
+        let unit = (Unit::meter() / Unit::second()).canonicalized();
+
+        let ascii = UnitFormatOptions {
+            times_separator: '*',
+            use_solidus: false,
+            ascii_exponents: true,
+        };
+        assert_eq!(unit.to_string_with(ascii), "m*s^-1");
+
+        let unicode = UnitFormatOptions {
+            use_solidus: false,
+            ..UnitFormatOptions::default()
+        };
+        assert_eq!(unit.to_string_with(unicode), "m·s⁻¹");
+
+        assert_eq!(unit.to_string_with(UnitFormatOptions::default()), "m/s");
+
+        let unit = (Unit::kilogram() / (Unit::meter() * Unit::second().powi(2))).canonicalized();
+        assert_eq!(
+            unit.to_string_with(UnitFormatOptions::default()),
+            unit.to_string()
+        );
+        assert_eq!(
+            unit.to_string_with(UnitFormatOptions {
+                times_separator: ' ',
+                use_solidus: true,
+                ascii_exponents: true,
+            }),
+            "kg/(m s^2)"
+        );
+    }
+
     #[test]
     fn is_multiple_of_basic() {
         assert_eq!(
@@ -0,0 +1,251 @@
+//! A small parser for unit expressions like `kg*m/s^2`. This allows units to be
+//! constructed from strings, independent of the full Numbat language.
+
+use itertools::Itertools;
+use thiserror::Error;
+
+use crate::{arithmetic::Rational, prefix::Prefix, prefix_parser::PrefixParser, unit::Unit};
+
+#[derive(Debug, Clone, Error, PartialEq, Eq)]
+pub enum UnitParseError {
+    #[error("Unknown unit '{0}'")]
+    UnknownUnit(String),
+
+    #[error("Unexpected character in unit expression: '{0}'")]
+    UnexpectedCharacter(char),
+
+    #[error("Unexpected end of unit expression")]
+    UnexpectedEnd,
+
+    #[error("Expected an integer exponent")]
+    InvalidExponent,
+}
+
+type Result<T> = std::result::Result<T, UnitParseError>;
+
+fn is_identifier_char(c: char) -> bool {
+    c.is_alphabetic() || matches!(c, '_' | '°' | '%' | '‰' | '′' | '″')
+}
+
+struct UnitParser<F> {
+    chars: Vec<char>,
+    position: usize,
+    lookup: F,
+}
+
+impl<F: Fn(&str) -> Option<Unit>> UnitParser<F> {
+    fn peek(&mut self) -> Option<char> {
+        while self
+            .chars
+            .get(self.position)
+            .is_some_and(|c| c.is_whitespace())
+        {
+            self.position += 1;
+        }
+        self.chars.get(self.position).copied()
+    }
+
+    fn advance(&mut self) {
+        self.position += 1;
+    }
+
+    fn expect(&mut self, expected: char) -> Result<()> {
+        match self.peek() {
+            Some(c) if c == expected => {
+                self.advance();
+                Ok(())
+            }
+            Some(c) => Err(UnitParseError::UnexpectedCharacter(c)),
+            None => Err(UnitParseError::UnexpectedEnd),
+        }
+    }
+
+    fn expression(&mut self) -> Result<Unit> {
+        let mut unit = self.power()?;
+        loop {
+            match self.peek() {
+                Some('*' | '·' | '×') => {
+                    self.advance();
+                    unit = unit * self.power()?;
+                }
+                Some('/') => {
+                    self.advance();
+                    unit = unit / self.power()?;
+                }
+                _ => return Ok(unit),
+            }
+        }
+    }
+
+    fn power(&mut self) -> Result<Unit> {
+        let unit = self.primary()?;
+        if self.peek() == Some('^') {
+            self.advance();
+            Ok(unit.powi(self.exponent()?))
+        } else {
+            Ok(unit)
+        }
+    }
+
+    fn exponent(&mut self) -> Result<i128> {
+        let parenthesized = self.peek() == Some('(');
+        if parenthesized {
+            self.advance();
+        }
+
+        let mut digits = String::new();
+        if let Some(sign @ ('+' | '-')) = self.peek() {
+            digits.push(sign);
+            self.advance();
+        }
+        while let Some(&digit) = self.chars.get(self.position) {
+            if !digit.is_ascii_digit() {
+                break;
+            }
+            digits.push(digit);
+            self.advance();
+        }
+        let exponent = digits
+            .parse()
+            .map_err(|_| UnitParseError::InvalidExponent)?;
+
+        if parenthesized {
+            self.expect(')')?;
+        }
+        Ok(exponent)
+    }
+
+    fn primary(&mut self) -> Result<Unit> {
+        match self.peek() {
+            Some('(') => {
+                self.advance();
+                let unit = self.expression()?;
+                self.expect(')')?;
+                Ok(unit)
+            }
+            Some('1') => {
+                self.advance();
+                Ok(Unit::scalar())
+            }
+            Some(c) if is_identifier_char(c) => {
+                let start = self.position;
+                while self
+                    .chars
+                    .get(self.position)
+                    .is_some_and(|&c| is_identifier_char(c))
+                {
+                    self.advance();
+                }
+                let name: String = self.chars[start..self.position].iter().collect();
+                self.resolve(&name)
+            }
+            Some(c) => Err(UnitParseError::UnexpectedCharacter(c)),
+            None => Err(UnitParseError::UnexpectedEnd),
+        }
+    }
+
+    /// Looks up a unit name. If it is not known, we try to split off a metric or
+    /// binary prefix (`km` → `k` + `m`) and look up the remaining name instead.
+    fn resolve(&self, name: &str) -> Result<Unit> {
+        if let Some(unit) = (self.lookup)(name) {
+            return Ok(unit);
+        }
+
+        PrefixParser::prefixes()
+            .iter()
+            .flat_map(|(long, shorts, prefix)| {
+                std::iter::once(long)
+                    .chain(shorts.iter())
+                    .map(move |p| (p, prefix))
+            })
+            .find_map(|(p, prefix)| {
+                let unit = name
+                    .strip_prefix(p)
+                    .filter(|rest| !rest.is_empty())
+                    .and_then(&self.lookup)?;
+                let accepts_prefix = unit.iter().exactly_one().is_ok_and(|factor| {
+                    factor.prefix == Prefix::none() && factor.exponent == Rational::from_integer(1)
+                });
+                accepts_prefix.then(|| unit.with_prefix(prefix.clone()))
+            })
+            .ok_or_else(|| UnitParseError::UnknownUnit(name.into()))
+    }
+}
+
+impl Unit {
+    /// Parses a unit expression like `kg*m/s^2` or `m^-1`. Unit names are resolved
+    /// with the given `lookup` function, prefixes are split off automatically if the
+    /// full name is not known.
+    pub fn parse(input: &str, lookup: impl Fn(&str) -> Option<Unit>) -> Result<Unit> {
+        let mut parser = UnitParser {
+            chars: input.chars().collect(),
+            position: 0,
+            lookup,
+        };
+
+        let unit = parser.expression()?;
+        match parser.peek() {
+            Some(c) => Err(UnitParseError::UnexpectedCharacter(c)),
+            None => Ok(unit),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lookup(name: &str) -> Option<Unit> {
+        match name {
+            "m" | "meter" => Some(Unit::meter()),
+            "s" | "second" => Some(Unit::second()),
+            "g" | "gram" => Some(Unit::gram()),
+            "h" | "hour" => Some(Unit::hour()),
+            "N" => Some(Unit::newton()),
+            _ => None,
+        }
+    }
+
+    fn parse(input: &str) -> Result<Unit> {
+        Unit::parse(input, lookup)
+    }
+
+    #[test]
+    fn basic() {
+        assert_eq!(parse("m"), Ok(Unit::meter()));
+        assert_eq!(parse("m/s"), Ok(Unit::meter() / Unit::second()));
+        assert_eq!(
+            parse("kg*m/s^2"),
+            Ok(Unit::kilogram() * Unit::meter() / Unit::second().powi(2))
+        );
+        assert_eq!(parse("m^-1"), Ok(Unit::meter().powi(-1)));
+        assert_eq!(parse("m^(-1)"), Ok(Unit::meter().powi(-1)));
+        assert_eq!(parse("1/s"), Ok(Unit::scalar() / Unit::second()));
+        assert_eq!(
+            parse(" N · m / (s * s) "),
+            Ok(Unit::newton() * Unit::meter() / (Unit::second() * Unit::second()))
+        );
+    }
+
+    #[test]
+    fn prefixes() {
+        assert_eq!(parse("km"), Ok(Unit::kilometer()));
+        assert_eq!(parse("kilometer"), Ok(Unit::kilometer()));
+        assert_eq!(parse("mm"), Ok(Unit::millimeter()));
+        assert_eq!(parse("km/h"), Ok(Unit::kilometer() / Unit::hour()));
+        assert_eq!(parse("kN"), Ok(Unit::newton().with_prefix(Prefix::kilo())));
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(
+            parse("m/bananas"),
+            Err(UnitParseError::UnknownUnit("bananas".into()))
+        );
+        assert_eq!(parse("m/"), Err(UnitParseError::UnexpectedEnd));
+        assert_eq!(parse("(m/s"), Err(UnitParseError::UnexpectedEnd));
+        assert_eq!(parse("m^x"), Err(UnitParseError::InvalidExponent));
+        assert_eq!(parse("m s"), Err(UnitParseError::UnexpectedCharacter('s')));
+        assert_eq!(parse(""), Err(UnitParseError::UnexpectedEnd));
+    }
+}
@@ -23,6 +23,8 @@ pub struct UnitMetadata {
     pub canonical_name: CanonicalName,
     pub url: Option<String>,
     pub description: Option<String>,
+    /// `Some(suggestion)` if the unit is marked as `@deprecated`
+    pub deprecated: Option<Option<String>>,
     pub binary_prefixes: bool,
     pub metric_prefixes: bool,
 }
@@ -51,9 +53,9 @@ impl UnitRegistry {
         base_representation: &Unit,
         metadata: UnitMetadata,
     ) -> Result<()> {
-        let base_representation_factors = base_representation
-            .iter()
-            .map(|factor| BaseRepresentationFactor(factor.unit_id.name.clone(), factor.exponent));
+        let base_representation_factors = base_representation.iter().map(|factor| {
+            BaseRepresentationFactor(factor.unit_id.name().to_string(), factor.exponent)
+        });
         let base_representation = BaseRepresentation::from_factors(base_representation_factors);
         self.inner
             .add_derived_entry(name, base_representation, metadata)
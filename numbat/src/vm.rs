@@ -13,7 +13,7 @@ use crate::{
     number::Number,
     prefix::Prefix,
     quantity::{Quantity, QuantityError},
-    unit::Unit,
+    unit::{PrefixSupport, Unit},
     unit_registry::{UnitMetadata, UnitRegistry},
     value::{FunctionReference, Value},
 };
@@ -623,7 +623,7 @@ impl Vm {
                 Op::ApplyPrefix => {
                     let quantity = self.pop_quantity();
                     let prefix_idx = self.read_u16();
-                    let prefix = self.prefixes[prefix_idx as usize];
+                    let prefix = self.prefixes[prefix_idx as usize].clone();
                     self.push_quantity(Quantity::new(
                         *quantity.unsafe_value(),
                         quantity.unit().clone().with_prefix(prefix),
@@ -648,12 +648,18 @@ impl Vm {
                         )
                         .map_err(RuntimeError::UnitRegistryError)?;
 
-                    self.constants[constant_idx as usize] = Constant::Unit(Unit::new_derived(
-                        &unit_information.0,
-                        unit_information.2.canonical_name.clone(),
-                        *conversion_value.unsafe_value(),
-                        defining_unit.clone(),
-                    ));
+                    self.constants[constant_idx as usize] = Constant::Unit(
+                        Unit::new_derived(
+                            &unit_information.0,
+                            unit_information.2.canonical_name.clone(),
+                            *conversion_value.unsafe_value(),
+                            defining_unit.clone(),
+                        )
+                        .with_prefix_support(PrefixSupport {
+                            metric: unit_information.2.metric_prefixes,
+                            binary: unit_information.2.binary_prefixes,
+                        }),
+                    );
                 }
                 Op::GetLocal => {
                     let slot_idx = self.read_u16() as usize;
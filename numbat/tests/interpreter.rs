@@ -474,6 +474,18 @@ fn test_prefixes() {
     expect_output("quettahertz quectosecond", "1");
 }
 
+#[test]
+fn test_unit_display_names() {
+    expect_output("2 d", "2 day");
+    expect_output("2 U", "2 RU");
+    expect_output("1 AU", "1 au");
+    expect_output("1 liter", "1 L");
+    expect_output("3 ml", "3 mL");
+    expect_output("1 byte", "1 B");
+    expect_output("1 nmi", "1 NM");
+    expect_output("1 kt", "1 kn");
+}
+
 #[test]
 fn test_parse_errors() {
     expect_failure(